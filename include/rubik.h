@@ -0,0 +1,108 @@
+#ifndef RUBIK_H
+#define RUBIK_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum {
+  RUBIK_STATUS_OK = 0,
+  // A null pointer, or a string which is not UTF-8.
+  RUBIK_STATUS_INVALID_ARGUMENT = 1,
+  // The facelets do not make a solvable cube, or the scramble is not a
+  // valid move sequence.
+  RUBIK_STATUS_INVALID_CUBE = 2,
+  // None within the maximum depth, or before the timeout.
+  RUBIK_STATUS_NO_SOLUTION = 3,
+} RubikStatus;
+
+// Loaded tables, opaque to C.
+typedef struct RubikTables RubikTables;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Free a string returned by the library.
+//
+// # Safety
+//
+// `solution` must be null or a string returned by `rubik_scramble`,
+// `rubik_solve_facelets` or `rubik_solve_scramble`, not freed yet, and
+// never freed any other way.
+void rubik_free_solution(char *solution);
+
+// `length` random moves from `seed`, to free with `rubik_free_solution`.
+char *rubik_scramble(uint32_t length, uint64_t seed);
+
+// Solve the cube with these facelets, as in `rubik_verify_facelets`, in at
+// most `max_depth` moves, giving up after `timeout_ms` milliseconds unless
+// it is 0.
+//
+// On success, `*solution` is set to the moves separated by spaces.
+// Free it with `rubik_free_solution`.
+//
+// # Safety
+//
+// `tables` must be null or a valid `RubikTables*` from `rubik_tables_load`
+// or `rubik_tables_from_blob`, `facelets` null or a NUL-terminated string,
+// and `solution` null or point to a writable `char*`.
+RubikStatus rubik_solve_facelets(const RubikTables *tables,
+                                 const char *facelets,
+                                 uint8_t max_depth,
+                                 uint32_t timeout_ms,
+                                 char **solution);
+
+// Solve the cube `scramble` leads to, like `rubik_solve_facelets`.
+//
+// # Safety
+//
+// As for `rubik_solve_facelets`, `scramble` being null or a NUL-terminated
+// string.
+RubikStatus rubik_solve_scramble(const RubikTables *tables,
+                                 const char *scramble,
+                                 uint8_t max_depth,
+                                 uint32_t timeout_ms,
+                                 char **solution);
+
+// Free tables returned by `rubik_tables_load` or `rubik_tables_from_blob`.
+//
+// # Safety
+//
+// `tables` must be null or a `RubikTables*` returned by one of them, not
+// freed yet. It must not be used afterwards.
+void rubik_tables_free(RubikTables *tables);
+
+// Load the tables from a blob written by `rubik tables`.
+//
+// Returns null if the blob is invalid.
+//
+// # Safety
+//
+// `data` must be null or point to `len` readable bytes.
+RubikTables *rubik_tables_from_blob(const uint8_t *data, size_t len);
+
+// Read the tables from the `cache_dir` folder, or generate and cache them
+// there. `pruning_tables` if `cache_dir` is null.
+//
+// Free the tables with `rubik_tables_free`.
+//
+// # Safety
+//
+// `cache_dir` must be null or a NUL-terminated string.
+RubikTables *rubik_tables_load(const char *cache_dir);
+
+// Check that `facelets`, 54 letters of the western color scheme for the U,
+// R, F, D, L and B faces, each face row by row, make a solvable cube.
+//
+// # Safety
+//
+// `facelets` must be null or a NUL-terminated string.
+RubikStatus rubik_verify_facelets(const char *facelets);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* RUBIK_H */
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use algorithms::COLL;
+use algorithms::Case;
+use algorithms::OLL;
+use algorithms::PLL;
+use algorithms::recognition::Recognition;
+use algorithms::recognition::u_turns;
+use cube::Cube;
+use move_::UserMove;
+use solver::partial::Goal;
+
+/// A set of last layer algorithms.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub enum Set {
+    /// Orienting the last layer.
+    Oll,
+    /// Permuting the last layer once oriented.
+    Pll,
+    /// Solving the corners of the last layer once its edges are oriented.
+    Coll,
+}
+
+impl Set {
+    pub const ALL: [Set; 3] = [Set::Oll, Set::Pll, Set::Coll];
+
+    pub fn cases(&self) -> &'static [Case] {
+        match *self {
+            Set::Oll => &OLL,
+            Set::Pll => &PLL,
+            Set::Coll => &COLL,
+        }
+    }
+
+    /// What the algorithms of the set solve in the last layer of `cube`:
+    /// the permutations then the orientations of the U corners, then of the
+    /// U edges, the other ones left to 0.
+    fn state(&self, cube: &Cube) -> [u8; 16] {
+        let (corners, edges) = (cube.corners(), cube.edges());
+        let mut state = [0; 16];
+
+        for i in 0..4 {
+            if *self != Set::Oll {
+                state[i] = usize::from(corners.permutations[i]) as u8;
+            }
+            state[i + 4] = corners.orientations[i];
+            if *self == Set::Pll {
+                state[i + 8] = usize::from(edges.permutations[i]) as u8;
+            }
+            state[i + 12] = edges.orientations[i];
+        }
+        state
+    }
+
+    /// The same `state` for all the cubes a U turn before or after `cube`
+    /// leads to.
+    fn key(&self, cube: &Cube) -> [u8; 16] {
+        let mut key = self.state(cube);
+
+        for before in 0..4 {
+            for after in 0..4 {
+                let mut turned = u_turns(before);
+                turned.multiply_by(cube);
+                turned.multiply_by(&u_turns(after));
+                key = key.min(self.state(&turned));
+            }
+        }
+        key
+    }
+}
+
+/// Lengths in the half turn then the quarter turn metric.
+fn lengths(case: &Case) -> (usize, usize) {
+    let sequence = case.sequence();
+
+    (sequence.len(), UserMove::quarter_turns(&sequence))
+}
+
+/// The cases of all the sets, by the last layer state they solve.
+pub struct Library {
+    cases: HashMap<(Set, [u8; 16]), Vec<&'static Case>>,
+}
+
+impl Library {
+    pub fn new() -> Self {
+        let mut cases = HashMap::new();
+
+        for &set in Set::ALL.iter() {
+            for case in set.cases() {
+                let state = Cube::from_shuffle_sequence(case.sequence()).inverse();
+                cases.entry((set, set.key(&state))).or_insert_with(Vec::new).push(case);
+            }
+        }
+        Self { cases: cases }
+    }
+
+    /// The algorithms of `set` for the last layer of `cube`, shortest first,
+    /// with the U turns to do around them.
+    ///
+    /// Returns nothing if the first two layers of `cube` are not solved.
+    pub fn lookup(&self, cube: &Cube, set: Set) -> Vec<Recognition> {
+        if !Goal::f2l().is_reached(cube) {
+            return Vec::new();
+        }
+        let solved = set.state(&Cube::new_default());
+        let mut cases = match self.cases.get(&(set, set.key(cube))) {
+            Some(cases) => cases.clone(),
+            None => return Vec::new(),
+        };
+        cases.sort_by_key(|case| lengths(case));
+
+        cases.into_iter()
+            .filter_map(|case| {
+                let algorithm = Cube::from_shuffle_sequence(case.sequence());
+                for pre_auf in 0..4 {
+                    for post_auf in 0..4 {
+                        let mut result = cube.clone();
+                        result.multiply_by(&u_turns(pre_auf));
+                        result.multiply_by(&algorithm);
+                        result.multiply_by(&u_turns(post_auf));
+                        if set.state(&result) == solved {
+                            return Some(Recognition { case: case, pre_auf: pre_auf, post_auf: post_auf });
+                        }
+                    }
+                }
+                None
+            })
+            .collect()
+    }
+
+    /// The algorithms of every set for the last layer of `cube`, shortest
+    /// first.
+    pub fn lookup_all(&self, cube: &Cube) -> Vec<Recognition> {
+        let mut recognitions: Vec<Recognition> = Set::ALL.iter()
+            .flat_map(|&set| self.lookup(cube, set))
+            .collect();
+
+        recognitions.sort_by_key(|recognition| lengths(recognition.case));
+        recognitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use algorithms::library::Library;
+    use algorithms::library::Set;
+    use algorithms::recognition::u_turns;
+    use cube::Cube;
+
+    #[test]
+    fn lookup_every_case() {
+        let library = Library::new();
+        let solved = Cube::new_default();
+
+        for &set in Set::ALL.iter() {
+            for case in set.cases() {
+                for pre_auf in 0..4 {
+                    let mut cube = Cube::from_shuffle_sequence(case.sequence()).inverse();
+                    cube.multiply_by(&u_turns(pre_auf));
+
+                    let recognitions = library.lookup(&cube, set);
+                    assert!(recognitions.iter().any(|recognition| recognition.case.name == case.name), "{}", case.name);
+                    for recognition in recognitions {
+                        let mut result = cube.clone();
+                        result.multiply_by(&Cube::from_shuffle_sequence(recognition.moves()));
+                        assert!(set.state(&result) == set.state(&solved), "{}", case.name);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+pub mod library;
+pub mod recognition;
+
+use move_::UserMove;
+
+/// A named last layer case and a face turn algorithm solving it.
+pub struct Case {
+    pub name: &'static str,
+    pub algorithm: &'static str,
+}
+
+impl Case {
+    pub fn sequence(&self) -> Vec<(UserMove, usize)> {
+        UserMove::sequence_from_str(self.algorithm).unwrap()
+    }
+}
+
+/// The 57 cases orienting the last layer, numbered as usual.
+pub const OLL: [Case; 57] = [
+    Case { name: "OLL 1", algorithm: "R U2 R2 F R F' U2 R' F R F'" },
+    Case { name: "OLL 2", algorithm: "F R U R' U' F' B U L U' L' B'" },
+    Case { name: "OLL 3", algorithm: "B U L U' L' B' U' F R U R' U' F'" },
+    Case { name: "OLL 4", algorithm: "B U L U' L' B' U F R U R' U' F'" },
+    Case { name: "OLL 5", algorithm: "R' F2 L F L' F R" },
+    Case { name: "OLL 6", algorithm: "L F2 R' F' R F' L'" },
+    Case { name: "OLL 7", algorithm: "L F R' F R F2 L'" },
+    Case { name: "OLL 8", algorithm: "R' F' L F' L' F2 R" },
+    Case { name: "OLL 9", algorithm: "R U R' U' R' F R2 U R' U' F'" },
+    Case { name: "OLL 10", algorithm: "R U R' U R' F R F' R U2 R'" },
+    Case { name: "OLL 11", algorithm: "L F R' F R' D R D' R F2 L'" },
+    Case { name: "OLL 12", algorithm: "R2 L F' R F' R' F2 R F' R L'" },
+    Case { name: "OLL 13", algorithm: "F U R U' R2 F' R U R U' R'" },
+    Case { name: "OLL 14", algorithm: "R' F R U R' F' R F U' F'" },
+    Case { name: "OLL 15", algorithm: "R' F' R L' U' L U R' F R" },
+    Case { name: "OLL 16", algorithm: "L F L' R U R' U' L F' L'" },
+    Case { name: "OLL 17", algorithm: "R U R' U R' F R F' U2 R' F R F'" },
+    Case { name: "OLL 18", algorithm: "L F R' F R F2 L2 B' R B' R' B2 L" },
+    Case { name: "OLL 19", algorithm: "L' R B R B R' B' R2 L F R F'" },
+    Case { name: "OLL 20", algorithm: "L F R' F' R2 L2 B R B' R' B' R' L" },
+    Case { name: "OLL 21", algorithm: "R U2 R' U' R U R' U' R U' R'" },
+    Case { name: "OLL 22", algorithm: "R U2 R2 U' R2 U' R2 U2 R" },
+    Case { name: "OLL 23", algorithm: "R2 D' R U2 R' D R U2 R" },
+    Case { name: "OLL 24", algorithm: "L F R' F' L' F R F'" },
+    Case { name: "OLL 25", algorithm: "F' L F R' F' L' F R" },
+    Case { name: "OLL 26", algorithm: "R U2 R' U' R U' R'" },
+    Case { name: "OLL 27", algorithm: "R U R' U R U2 R'" },
+    Case { name: "OLL 28", algorithm: "L F R' F' L' R U R U' R'" },
+    Case { name: "OLL 29", algorithm: "R U R' U' R U' R' F' U' F R U R'" },
+    Case { name: "OLL 30", algorithm: "F R' F R2 U' R' U' R U R' F2" },
+    Case { name: "OLL 31", algorithm: "R' U' F U R U' R' F' R" },
+    Case { name: "OLL 32", algorithm: "L U F' U' L' U L F L'" },
+    Case { name: "OLL 33", algorithm: "R U R' U' R' F R F'" },
+    Case { name: "OLL 34", algorithm: "R U R2 U' R' F R U R U' F'" },
+    Case { name: "OLL 35", algorithm: "R U2 R2 F R F' R U2 R'" },
+    Case { name: "OLL 36", algorithm: "L' U' L U' L' U L U L F' L' F" },
+    Case { name: "OLL 37", algorithm: "F R' F' R U R U' R'" },
+    Case { name: "OLL 38", algorithm: "R U R' U R U' R' U' R' F R F'" },
+    Case { name: "OLL 39", algorithm: "L F' L' U' L U F U' L'" },
+    Case { name: "OLL 40", algorithm: "R' F R U R' U' F' U R" },
+    Case { name: "OLL 41", algorithm: "R U R' U R U2 R' F R U R' U' F'" },
+    Case { name: "OLL 42", algorithm: "R' U' R U' R' U2 R F R U R' U' F'" },
+    Case { name: "OLL 43", algorithm: "B' U' R' U R B" },
+    Case { name: "OLL 44", algorithm: "B U L U' L' B'" },
+    Case { name: "OLL 45", algorithm: "F R U R' U' F'" },
+    Case { name: "OLL 46", algorithm: "R' U' R' F R F' U R" },
+    Case { name: "OLL 47", algorithm: "R' U' R' F R F' R' F R F' U R" },
+    Case { name: "OLL 48", algorithm: "F R U R' U' R U R' U' F'" },
+    Case { name: "OLL 49", algorithm: "L F' L2 B L2 F L2 B' L" },
+    Case { name: "OLL 50", algorithm: "L' B L2 F' L2 B' L2 F L'" },
+    Case { name: "OLL 51", algorithm: "F U R U' R' U R U' R' F'" },
+    Case { name: "OLL 52", algorithm: "R U R' U R U' B U' B' R'" },
+    Case { name: "OLL 53", algorithm: "R' F2 L F L' F' L F L' F R" },
+    Case { name: "OLL 54", algorithm: "L F2 R' F' R F R' F' R F' L'" },
+    Case { name: "OLL 55", algorithm: "R' F R U R U' R2 F' R2 U' R' U R U R'" },
+    Case { name: "OLL 56", algorithm: "L' B' L U' R' U R U' R' U R L' B L" },
+    Case { name: "OLL 57", algorithm: "R U R' U' R' L F R F' L'" },
+];
+
+/// The 21 cases permuting the last layer once it is oriented.
+pub const PLL: [Case; 21] = [
+    Case { name: "Aa perm", algorithm: "R' F R' B2 R F' R' B2 R2" },
+    Case { name: "Ab perm", algorithm: "R2 B2 R F R' B2 R F' R" },
+    Case { name: "E perm", algorithm: "R B' R' F R B R' F' R B R' F R B' R' F'" },
+    Case { name: "F perm", algorithm: "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R" },
+    Case { name: "Ga perm", algorithm: "R2 U R' U R' U' R U' R2 U' D R' U R D'" },
+    Case { name: "Gb perm", algorithm: "R' U' R U D' R2 U R' U R U' R U' R2 D" },
+    Case { name: "Gc perm", algorithm: "R2 U' R U' R U R' U R2 U D' R U' R' D" },
+    Case { name: "Gd perm", algorithm: "R U R' U' D R2 U' R U' R' U R' U R2 D'" },
+    Case { name: "H perm", algorithm: "R2 L2 D R2 L2 U2 R2 L2 D R2 L2" },
+    Case { name: "Ja perm", algorithm: "R' U L' U2 R U' R' U2 R L" },
+    Case { name: "Jb perm", algorithm: "R U R' F' R U R' U' R' F R2 U' R'" },
+    Case { name: "Na perm", algorithm: "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'" },
+    Case { name: "Nb perm", algorithm: "R' U R U' R' F' U' F R U R' F R' F' R U' R" },
+    Case { name: "Ra perm", algorithm: "R U' R' U' R U R D R' U' R D' R' U2 R'" },
+    Case { name: "Rb perm", algorithm: "R2 F R U R U' R' F' R U2 R' U2 R" },
+    Case { name: "T perm", algorithm: "R U R' U' R' F R2 U' R' U' R U R' F'" },
+    Case { name: "Ua perm", algorithm: "R U' R U R U R U' R' U' R2" },
+    Case { name: "Ub perm", algorithm: "R2 U R U R' U' R' U' R' U R'" },
+    Case { name: "V perm", algorithm: "R' U R' U' B' R' B2 U' B' U B' R B R" },
+    Case { name: "Y perm", algorithm: "F R U' R' U' R U R' F' R U R' U' R' F R F'" },
+    Case { name: "Z perm", algorithm: "R' L F R2 L2 B R2 L2 F R' L D2 R2 L2" },
+];
+
+/// Cases solving the corners of the last layer once its edges are oriented:
+/// some of them, not all 42.
+///
+/// The names are not the ones of a published list: each case is named after
+/// the orientation of its corners, as the OLL case of the same name, then
+/// numbered in order of first appearance of the state it solves, so that
+/// the algorithms sharing a name solve the same state up to U turns.
+pub const COLL: [Case; 27] = [
+    Case { name: "COLL Sune 1", algorithm: "R U R' U R U2 R'" },
+    Case { name: "COLL Sune 1", algorithm: "L' U2 L U L' U L" },
+    Case { name: "COLL Sune 1", algorithm: "R U2 R' U' R U' R' U2 R U2 R' U' R U' R'" },
+    Case { name: "COLL Sune 2", algorithm: "R U' L' U R' U' L" },
+    Case { name: "COLL Antisune 1", algorithm: "R U2 R' U' R U' R'" },
+    Case { name: "COLL Antisune 1", algorithm: "L' U' L U' L' U2 L" },
+    Case { name: "COLL Antisune 1", algorithm: "R' U' R U' R' U2 R" },
+    Case { name: "COLL Antisune 1", algorithm: "R U R' U R U2 R' U2 R U R' U R U2 R'" },
+    Case { name: "COLL Antisune 2", algorithm: "R2 D R' U R D' R' U R' U' R U' R'" },
+    Case { name: "COLL H 1", algorithm: "R U R' U R U' R' U R U2 R'" },
+    Case { name: "COLL H 1", algorithm: "R' U2 R U R' U R U R U R' U R U2 R'" },
+    Case { name: "COLL H 2", algorithm: "F R U R' U' R U R' U' R U R' U' F'" },
+    Case { name: "COLL Pi 1", algorithm: "R U2 R2 U' R2 U' R2 U2 R" },
+    Case { name: "COLL Pi 1", algorithm: "R U R' U R U2 R' U R U R' U R U2 R'" },
+    Case { name: "COLL Pi 1", algorithm: "R U R' U R U2 R' U' R U R' U R U2 R'" },
+    Case { name: "COLL Pi 1", algorithm: "R U2 R' U' R U' R' U R U2 R' U' R U' R'" },
+    Case { name: "COLL Pi 1", algorithm: "R U2 R' U' R U' R' U' R U2 R' U' R U' R'" },
+    Case { name: "COLL Pi 1", algorithm: "R' U' R U' R' U2 R U' R' U' R U' R' U2 R" },
+    Case { name: "COLL Pi 2", algorithm: "R U R' U' R' F R2 U R' U' R U R' U' F'" },
+    Case { name: "COLL U 1", algorithm: "R2 D R' U2 R D' R' U2 R'" },
+    Case { name: "COLL U 2", algorithm: "R2 D' R U2 R' D R U2 R" },
+    Case { name: "COLL U 3", algorithm: "R U R' U R U2 R' U R U2 R' U' R U' R'" },
+    Case { name: "COLL T 1", algorithm: "L F R' F' L' F R F'" },
+    Case { name: "COLL T 2", algorithm: "R U R' U R U2 R' U' R U2 R' U' R U' R'" },
+    Case { name: "COLL L 1", algorithm: "F' L F R' F' L' F R" },
+    Case { name: "COLL L 1", algorithm: "F R U' R' U' R U2 R' U' F'" },
+    Case { name: "COLL L 2", algorithm: "R U R' U R U' R' U R U' R' U R U2 R'" },
+];
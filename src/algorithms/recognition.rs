@@ -0,0 +1,168 @@
+use algorithms::Case;
+use algorithms::OLL;
+use algorithms::PLL;
+use cube::Cube;
+use move_::UserMove;
+use solver::partial::Goal;
+
+/// A recognized last layer case, with the number of U turns to do before
+/// and after its algorithm.
+pub struct Recognition {
+    pub case: &'static Case,
+    pub pre_auf: usize,
+    pub post_auf: usize,
+}
+
+impl Recognition {
+    pub fn moves(&self) -> Vec<(UserMove, usize)> {
+        let mut moves = Vec::new();
+
+        if self.pre_auf != 0 {
+            moves.push((UserMove::from_move(UserMove::Up.to_move(), self.pre_auf), self.pre_auf));
+        }
+        moves.extend(self.case.sequence());
+        if self.post_auf != 0 {
+            moves.push((UserMove::from_move(UserMove::Up.to_move(), self.post_auf), self.post_auf));
+        }
+        moves
+    }
+}
+
+/// Orientations of the U corners and edges.
+fn orientations(cube: &Cube) -> [u8; 8] {
+    let mut ret = [0; 8];
+
+    ret[..4].copy_from_slice(&cube.corners().orientations[..4]);
+    ret[4..].copy_from_slice(&cube.edges().orientations[..4]);
+    ret
+}
+
+/// Permutation of the U corners and edges.
+fn permutations(cube: &Cube) -> [usize; 8] {
+    let mut ret = [0; 8];
+
+    for x in 0..4 {
+        ret[x] = usize::from(cube.corners().permutations[x]);
+        ret[x + 4] = usize::from(cube.edges().permutations[x]);
+    }
+    ret
+}
+
+/// `n` quarter turns of U.
+pub fn u_turns(n: usize) -> Cube {
+    Cube::from_shuffle_sequence(vec![(UserMove::Up, n)])
+}
+
+/// State the algorithm of `case` solves, without any U turn around it.
+fn case_state(case: &Case) -> Cube {
+    Cube::from_shuffle_sequence(case.sequence()).inverse()
+}
+
+/// Which OLL case `cube` is in, provided its first two layers are solved.
+///
+/// Returns `None` if the first two layers are not solved, or if the last
+/// layer is already oriented.
+pub fn recognize_oll(cube: &Cube) -> Option<Recognition> {
+    if !Goal::f2l().is_reached(cube) || Goal::oll().is_reached(cube) {
+        return None;
+    }
+
+    // The orientation of the last layer after the algorithm only depends on
+    // the orientation before it, so it must match the one of the case.
+    let states: Vec<[u8; 8]> = OLL.iter().map(|case| orientations(&case_state(case))).collect();
+    for pre_auf in 0..4 {
+        let mut turned = cube.clone();
+        turned.multiply_by(&u_turns(pre_auf));
+        let pattern = orientations(&turned);
+
+        for (case, state) in OLL.iter().zip(states.iter()) {
+            if *state == pattern {
+                return Some(Recognition { case: case, pre_auf: pre_auf, post_auf: 0 });
+            }
+        }
+    }
+    None
+}
+
+/// Which PLL case `cube` is in, provided its last layer is oriented.
+///
+/// Returns `None` if the last layer is not oriented, or if it is already
+/// permuted up to a U turn.
+pub fn recognize_pll(cube: &Cube) -> Option<Recognition> {
+    if !Goal::oll().is_reached(cube) {
+        return None;
+    }
+    for post_auf in 0..4 {
+        let mut turned = cube.clone();
+        turned.multiply_by(&u_turns(post_auf));
+        if turned.is_solved() {
+            return None;
+        }
+    }
+
+    // `cube * U^pre * alg * U^post` is solved when `U^post * cube * U^pre`
+    // is `alg^-1`.
+    let states: Vec<[usize; 8]> = PLL.iter().map(|case| permutations(&case_state(case))).collect();
+    for pre_auf in 0..4 {
+        let mut turned = cube.clone();
+        turned.multiply_by(&u_turns(pre_auf));
+
+        for post_auf in 0..4 {
+            let mut expected = u_turns(post_auf);
+            expected.multiply_by(&turned);
+            let pattern = permutations(&expected);
+
+            for (case, state) in PLL.iter().zip(states.iter()) {
+                if *state == pattern {
+                    return Some(Recognition { case: case, pre_auf: pre_auf, post_auf: post_auf });
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use algorithms::OLL;
+    use algorithms::PLL;
+    use algorithms::recognition::case_state;
+    use algorithms::recognition::recognize_oll;
+    use algorithms::recognition::recognize_pll;
+    use algorithms::recognition::u_turns;
+    use cube::Cube;
+    use solver::partial::Goal;
+
+    #[test]
+    fn every_oll_case() {
+        for case in OLL.iter() {
+            for pre_auf in 0..4 {
+                let mut cube = case_state(case);
+                cube.multiply_by(&u_turns(pre_auf));
+
+                let recognition = recognize_oll(&cube).unwrap();
+                assert_eq!(recognition.case.name, case.name);
+                cube.multiply_by(&Cube::from_shuffle_sequence(recognition.moves()));
+                assert!(Goal::oll().is_reached(&cube));
+            }
+        }
+    }
+
+    #[test]
+    fn every_pll_case() {
+        for case in PLL.iter() {
+            for pre_auf in 0..4 {
+                for post_auf in 0..4 {
+                    let mut cube = u_turns(post_auf);
+                    cube.multiply_by(&case_state(case));
+                    cube.multiply_by(&u_turns(pre_auf));
+
+                    let recognition = recognize_pll(&cube).unwrap();
+                    assert_eq!(recognition.case.name, case.name);
+                    cube.multiply_by(&Cube::from_shuffle_sequence(recognition.moves()));
+                    assert!(cube.is_solved());
+                }
+            }
+        }
+    }
+}
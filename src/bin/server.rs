@@ -0,0 +1,324 @@
+//! Solving service over HTTP, listening on localhost.
+//!
+//! `POST /solve` takes a scramble or a facelet string as body and answers
+//! the JSON report of `rubik solve --format json`, `GET /scramble` a random
+//! scramble, `POST /verify` whether a facelet string makes a solvable cube,
+//! and `GET /render.svg` draws the cube given by its `cube` parameter.
+
+extern crate rubik;
+
+use std::collections::HashMap;
+use std::env;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::process;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use rubik::cli::read_cube;
+use rubik::cli::report::Report;
+use rubik::cli::report::json_string;
+use rubik::coordinate::Coordinate;
+use rubik::coordinate::TableLoad;
+use rubik::cube::Cube;
+use rubik::cube::color::ColorScheme;
+use rubik::move_::Notation;
+use rubik::move_::UserMove;
+use rubik::render::svg::Svg;
+use rubik::solver::Solver;
+
+const USAGE: &str = "Usage: server [--port <n>]
+
+Serves on http://127.0.0.1:<n>, 8080 by default:
+  POST /solve          Body: a scramble or sticker colors, answers JSON.
+                       Parameters: max_depth, timeout in seconds (default
+                       5, at most 60)
+  GET  /scramble       Parameters: seed, length
+  POST /verify         Body: sticker colors
+  GET  /render.svg     Parameters: cube, a scramble or sticker colors,
+                       view (net or isometric), next (a move)";
+
+/// Largest request accepted, headers and body together.
+const MAX_REQUEST_SIZE: usize = 64 * 1024;
+
+/// Seconds a solve may take when the request does not say.
+const DEFAULT_TIMEOUT: f64 = 5.0;
+
+/// Most seconds a request can ask a solve to take.
+const MAX_TIMEOUT: f64 = 60.0;
+
+/// Shared by all the connections.
+struct State {
+    coordinate: Coordinate,
+    tables: TableLoad,
+    tables_time: Duration,
+    scheme: ColorScheme,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Self { status: status, content_type: "application/json", body: body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, format!("{{\"error\":{}}}", json_string(message)))
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let port = match (args.get(0).map(|x| x.as_str()), args.get(1)) {
+        (None, _) => 8080,
+        (Some("--port"), Some(port)) if args.len() == 2 => match port.parse::<u16>() {
+            Ok(port) => port,
+            Err(_) => {
+                eprintln!("server: invalid port {}", port);
+                process::exit(2);
+            },
+        },
+        (Some("--help"), _) => {
+            println!("{}", USAGE);
+            return;
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        },
+    };
+
+    let scheme = match ColorScheme::try_from_env() {
+        Ok(scheme) => scheme,
+        Err(e) => {
+            eprintln!("server: {}", e);
+            process::exit(2);
+        },
+    };
+    let start = Instant::now();
+    let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+    let tables = coordinate.init_pruning();
+    let state = Arc::new(State {
+        coordinate: coordinate,
+        tables: tables,
+        tables_time: start.elapsed(),
+        scheme: scheme,
+    });
+
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("server: cannot listen on port {}: {}", port, e);
+            process::exit(1);
+        },
+    };
+    eprintln!("listening on http://127.0.0.1:{}", port);
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("server: {}", e);
+                continue;
+            },
+        };
+        let state = state.clone();
+
+        thread::spawn(move || {
+            if let Err(e) = handle(stream, &state) {
+                eprintln!("server: {}", e);
+            }
+        });
+    }
+}
+
+fn handle(mut stream: TcpStream, state: &State) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+
+    let response = match read_request(&stream) {
+        Ok(request) => route(&request, state),
+        Err(response) => response,
+    };
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Unprocessable Entity",
+    };
+
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           response.status, reason, response.content_type, response.body.len(), response.body)?;
+    stream.flush()
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, Response> {
+    let mut reader = BufReader::new(stream.take(MAX_REQUEST_SIZE as u64));
+    let mut line = String::new();
+
+    reader.read_line(&mut line).map_err(|e| Response::error(400, &e.to_string()))?;
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() != 3 {
+        return Err(Response::error(400, "malformed request line"));
+    }
+    let (method, target) = (parts[0].to_string(), parts[1].to_string());
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).map_err(|e| Response::error(400, &e.to_string()))? == 0 {
+            return Err(Response::error(400, "incomplete headers"));
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let mut split = header.splitn(2, ':');
+        let (name, value) = (split.next().unwrap_or(""), split.next().unwrap_or("").trim());
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().map_err(|_| Response::error(400, "invalid Content-Length"))?;
+        }
+    }
+    if content_length > MAX_REQUEST_SIZE {
+        return Err(Response::error(413, "request too large"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(|e| Response::error(400, &e.to_string()))?;
+    let body = String::from_utf8(body).map_err(|_| Response::error(400, "body is not UTF-8"))?;
+
+    let mut split = target.splitn(2, '?');
+    let path = split.next().unwrap_or("").to_string();
+    let query = split.next().unwrap_or("").split('&')
+        .filter(|x| !x.is_empty())
+        .map(|pair| {
+            let mut split = pair.splitn(2, '=');
+            (url_decode(split.next().unwrap_or("")), url_decode(split.next().unwrap_or("")))
+        })
+        .collect();
+
+    Ok(Request { method: method, path: path, query: query, body: body })
+}
+
+/// Decode `+` and `%XX` escapes.
+fn url_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if s.get(i + 1..i + 3).map_or(false, |x| u8::from_str_radix(x, 16).is_ok()) => {
+                decoded.push(u8::from_str_radix(&s[i + 1..i + 3], 16).unwrap());
+                i += 2;
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Value of a numeric query parameter, `default` if it is missing.
+fn parameter<T: std::str::FromStr>(request: &Request, name: &str, default: T) -> Result<T, Response> {
+    match request.query.get(name) {
+        Some(value) => value.parse().map_err(|_| Response::error(400, &format!("invalid {}", name))),
+        None => Ok(default),
+    }
+}
+
+fn route(request: &Request, state: &State) -> Response {
+    let result = match (request.method.as_str(), request.path.as_str()) {
+        ("POST", "/solve") => solve(request, state),
+        ("GET", "/scramble") => scramble(request),
+        ("POST", "/verify") => verify(request, state),
+        ("GET", "/render.svg") => render(request, state),
+        (_, "/solve") | (_, "/scramble") | (_, "/verify") | (_, "/render.svg") => {
+            Err(Response::error(405, "method not allowed"))
+        },
+        _ => Err(Response::error(404, "not found")),
+    };
+    match result {
+        Ok(response) => response,
+        Err(response) => response,
+    }
+}
+
+fn solve(request: &Request, state: &State) -> Result<Response, Response> {
+    let max_depth = parameter(request, "max_depth", 26)?;
+    let timeout: f64 = parameter(request, "timeout", DEFAULT_TIMEOUT)?;
+    if !(timeout > 0.0) {
+        return Err(Response::error(400, "invalid timeout"));
+    }
+    let solver = Solver::new(&state.coordinate)
+        .max_depth(max_depth)
+        .timeout(Duration::from_millis((timeout.min(MAX_TIMEOUT) * 1000.0) as u64));
+
+    let report = Report::two_phase(request.body.trim().to_string(), &solver, &state.scheme, Notation::Lenient,
+                                   state.tables, state.tables_time);
+    let status = if report.error.is_some() { 422 } else { 200 };
+    Ok(Response::json(status, report.json()))
+}
+
+fn scramble(request: &Request) -> Result<Response, Response> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seed = parameter(request, "seed", now.as_secs() ^ u64::from(now.subsec_nanos()))?;
+    let length = parameter(request, "length", 25)?;
+    if length > 1000 {
+        return Err(Response::error(400, "length above 1000"));
+    }
+
+    let scramble = UserMove::sequence_to_string(&UserMove::random_sequence(length, seed));
+    Ok(Response::json(200, format!("{{\"scramble\":{}}}", json_string(&scramble))))
+}
+
+fn verify(request: &Request, state: &State) -> Result<Response, Response> {
+    let body = match Cube::from_facelets(request.body.trim(), &state.scheme) {
+        Ok(_) => "{\"valid\":true}".to_string(),
+        Err(e) => format!("{{\"valid\":false,\"error\":{}}}", json_string(&e.to_string())),
+    };
+    Ok(Response::json(200, body))
+}
+
+fn render(request: &Request, state: &State) -> Result<Response, Response> {
+    let cube = match request.query.get("cube") {
+        Some(cube) => read_cube(cube, &state.scheme, Notation::Lenient).map_err(|e| Response::error(400, &e))?,
+        None => Cube::new_default(),
+    };
+    let mut svg = Svg::new().scheme(state.scheme);
+    if let Some(next) = request.query.get("next") {
+        match UserMove::sequence_from_notation(next, Notation::Strict) {
+            Ok(ref moves) if moves.len() == 1 => svg = svg.next_move(moves[0].0),
+            _ => return Err(Response::error(400, "invalid next move")),
+        }
+    }
+
+    let body = match request.query.get("view").map(|x| x.as_str()) {
+        None | Some("net") => svg.net(&cube),
+        Some("isometric") => svg.isometric(&cube),
+        Some(_) => return Err(Response::error(400, "unknown view")),
+    };
+    Ok(Response { status: 200, content_type: "image/svg+xml", body: body })
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use coordinate::Coordinate;
+use coordinate::TableLoad;
+use cube::color::ColorScheme;
+use move_::Notation;
+use move_::UserMove;
+use solver::Solver;
+
+use super::report::CSV_HEADER;
+use super::report::Format;
+use super::report::Report;
+
+/// Solves one cube per line of input, sharing the tables between worker
+/// threads.
+pub struct Batch {
+    pub threads: usize,
+    pub max_depth: u8,
+    pub timeout: Option<Duration>,
+    pub scheme: ColorScheme,
+    /// Of the scrambles.
+    pub notation: Notation,
+    /// Text, JSON or CSV.
+    pub format: Format,
+    /// How the shared tables were loaded, repeated in each report.
+    pub tables: TableLoad,
+    pub tables_time: Duration,
+}
+
+impl Batch {
+    /// Solve every non empty line of `input`, a scramble or a facelet
+    /// string, and write a result for each of them in the order of the
+    /// input: `<line>\t<solution>` or `<line>\terror: <why>` in text.
+    ///
+    /// Returns the number of lines which could not be solved, or an error
+    /// if a worker thread panicked, after writing an error line for each of
+    /// the lines it left unsolved.
+    pub fn run<R: BufRead, W: Write>(&self, coordinate: Arc<Coordinate>, input: R, out: &mut W) -> io::Result<usize> {
+        let (job_sender, job_receiver) = mpsc::channel::<(usize, String)>();
+        let (result_sender, result_receiver) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+
+        let mut workers = Vec::new();
+        for _ in 0..self.threads.max(1) {
+            let (coordinate, jobs, results) = (coordinate.clone(), job_receiver.clone(), result_sender.clone());
+            let (max_depth, timeout, scheme, notation) = (self.max_depth, self.timeout, self.scheme, self.notation);
+            let (tables, tables_time) = (self.tables, self.tables_time);
+
+            workers.push(thread::spawn(move || {
+                let mut solver = Solver::new(&coordinate).max_depth(max_depth);
+                if let Some(timeout) = timeout {
+                    solver = solver.timeout(timeout);
+                }
+                loop {
+                    let job = jobs.lock().unwrap().recv();
+                    let (index, line) = match job {
+                        Ok(job) => job,
+                        Err(_) => break,
+                    };
+                    let report = Report::two_phase(line, &solver, &scheme, notation, tables, tables_time);
+                    if results.send((index, report)).is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
+        drop(result_sender);
+
+        let mut pending = BTreeMap::new();
+        let mut written = 0;
+        let mut failures = 0;
+        let mut sent = 0;
+        let mut lines = Vec::new();
+        if self.format == Format::Csv {
+            writeln!(out, "{}", CSV_HEADER)?;
+        }
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            lines.push(line.trim().to_string());
+            let _ = job_sender.send((sent, line.trim().to_string()));
+            sent += 1;
+
+            while let Ok((index, report)) = result_receiver.try_recv() {
+                pending.insert(index, report);
+            }
+            self.write_ready(&mut pending, &mut written, &mut failures, out)?;
+        }
+        drop(job_sender);
+
+        for (index, report) in result_receiver.iter() {
+            pending.insert(index, report);
+            self.write_ready(&mut pending, &mut written, &mut failures, out)?;
+        }
+        let mut panicked = false;
+        for worker in workers {
+            panicked |= worker.join().is_err();
+        }
+        if !panicked {
+            return Ok(failures);
+        }
+
+        // The lines taken by a worker when it panicked have no report.
+        for index in written..sent {
+            pending.entry(index).or_insert_with(|| {
+                let mut report = Report::new(lines[index].clone(), "two-phase");
+                report.tables = self.tables.status().to_string();
+                report.tables_time = self.tables_time;
+                report.error = Some("the solver panicked".to_string());
+                report
+            });
+        }
+        self.write_ready(&mut pending, &mut written, &mut failures, out)?;
+        Err(io::Error::new(io::ErrorKind::Other, "a worker thread panicked"))
+    }
+
+    /// Write the results following the last written one.
+    fn write_ready<W: Write>(&self, pending: &mut BTreeMap<usize, Report>, written: &mut usize, failures: &mut usize,
+                             out: &mut W) -> io::Result<()> {
+        while let Some(report) = pending.remove(&*written) {
+            if report.error.is_some() {
+                *failures += 1;
+            }
+            match (self.format, &report.error) {
+                (Format::Json, _) => writeln!(out, "{}", report.json())?,
+                (Format::Csv, _) => writeln!(out, "{}", report.csv())?,
+                (_, &Some(ref error)) => writeln!(out, "{}\terror: {}", report.input, error)?,
+                (_, &None) => writeln!(out, "{}\t{}", report.input, UserMove::sequence_to_string(&report.solution))?,
+            }
+            *written += 1;
+        }
+        out.flush()
+    }
+}
@@ -0,0 +1,635 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use coordinate::Coordinate;
+use coordinate::store::Blob;
+use cube::Cube;
+use cube::color::ColorScheme;
+use cube::pattern::PATTERNS;
+use cube::pattern::Pattern;
+use move_::Notation;
+use move_::UserMove;
+use render;
+use render::Renderer;
+use render::playback::Playback;
+use render::svg::Svg;
+use solver::Solver;
+use solver::cfop::CfopSolver;
+use solver::thistlethwaite::Thistlethwaite;
+
+use self::batch::Batch;
+use self::report::CSV_HEADER;
+use self::report::Format;
+use self::report::Report;
+use self::report::numbered_phases;
+use self::stats::Statistics;
+
+mod batch;
+pub mod report;
+mod stats;
+
+const USAGE: &str = "Usage: rubik <command> [options]
+
+Commands:
+  solve <scramble>           Solve the cube a scramble leads to
+  solve --facelets <colors>  Solve the cube with these sticker colors
+  batch [file]               Solve one scramble or sticker colors per line
+  scramble                   Print a random scramble
+  show <sequence>            Draw the cube a sequence leads to
+  play <scramble>            Play a scramble and its solution move by move
+  verify <colors>            Check that sticker colors make a solvable cube
+  invert <sequence>          Print the sequence undoing a sequence
+  simplify <sequence>        Merge and cancel the turns of a sequence
+  cycles <sequence>          Print the order and piece cycles of a sequence
+  pattern [name]             Print the sequence leading to a named pattern
+  tables <file>              Save the pruning tables as a single blob
+  stats                      Solve random states and print statistics
+
+Run `rubik <command> --help` for the options of a command.
+
+Sequences may use commutators [A, B], conjugates [A: B], repetitions (A)3,
+and // or /* */ comments. Besides R, R' and R2, R2', R'2, R3, Ri, wide moves
+Rw and r, slices M, E and S, and rotations x, y and z are read as well,
+unless --strict is given.
+
+Sticker colors are 54 letters for the U, R, F, D, L and B faces, each face
+row by row, in the scheme set by RUBIK_COLORS: western (default), japanese,
+or the letters of the U, R, F, D, L and B faces, such as WRGYOB, or their
+colors separated by commas, each a letter optionally followed by #rrggbb.
+Cubes are drawn in color on a terminal, as plain letters when stdout is not
+a terminal or NO_COLOR is set, unless --color says otherwise.
+
+Exit codes: 0 on success, 1 when a cube is invalid or no solution is found,
+2 on usage errors.";
+
+const SOLVE_USAGE: &str = "Usage: rubik solve [options] <scramble>
+       rubik solve [options] --facelets <colors>
+
+Options:
+  --method <method>  two-phase (default), cfop or thistlethwaite
+  --max-depth <n>    Longest solution to look for, two-phase only (default 26)
+  --timeout <secs>   Give up after this many seconds, two-phase only
+  --format <format>  text (default): the cube then the solution,
+                     moves: the solution only,
+                     json: the input, solution, lengths in HTM and QTM,
+                     phases, timings and table load status,
+                     csv: the same as a header and one row
+  --color <renderer> auto (default), letters, blocks, ansi, ansi256 or
+                     truecolor, to draw the cube in text
+  --strict           Only read face turns written R, R' or R2
+
+Diagnostics, like the loading of the tables, go to stderr.";
+
+const BATCH_USAGE: &str = "Usage: rubik batch [options] [file]
+
+Reads stdin when no file is given, and writes one line per non empty input
+line: the input, a tab, then the solution or `error: ` and why it failed.
+Exits with 1 if any line failed, after solving all of them.
+
+Options:
+  --threads <n>      Number of worker threads (default 1)
+  --max-depth <n>    Longest solution to look for (default 26)
+  --timeout <secs>   Give up on a line after this many seconds
+  --format <format>  text (default), json for one JSON object per line, or
+                     csv for a header then one row per line, with the same
+                     fields as `rubik solve`
+  --strict           Only read face turns written R, R' or R2";
+
+const SCRAMBLE_USAGE: &str = "Usage: rubik scramble [options]
+
+Options:
+  --seed <n>    Seed of the random moves, the current time by default
+  --length <n>  Number of moves (default 25)";
+
+const SHOW_USAGE: &str = "Usage: rubik show [options] <sequence>
+
+Options:
+  --format <format>  text (default), svg for the net or isometric for a 3D
+                     view of the U, F and R faces, both in SVG
+  --next <move>      Draw an arrow for this face turn, SVG formats only
+  --color <renderer> auto (default), letters, blocks, ansi, ansi256 or
+                     truecolor, for the text format
+  --strict           Only read face turns written R, R' or R2";
+
+const PLAY_USAGE: &str = "Usage: rubik play [options] <scramble>
+
+Options:
+  --delay <ms>       Time between two moves (default 500)
+  --step             Wait for Enter before each move
+  --color <renderer> auto (default), letters, blocks, ansi, ansi256 or
+                     truecolor
+  --strict           Only read face turns written R, R' or R2";
+
+const VERIFY_USAGE: &str = "Usage: rubik verify <colors>
+
+Exits with 1 and tells why if the colors do not make a solvable cube.";
+
+const INVERT_USAGE: &str = "Usage: rubik invert [options] <sequence>
+
+Options:
+  --strict           Only read face turns written R, R' or R2";
+
+const SIMPLIFY_USAGE: &str = "Usage: rubik simplify [options] <sequence>
+
+Options:
+  --strict           Only read face turns written R, R' or R2";
+
+const CYCLES_USAGE: &str = "Usage: rubik cycles [options] <sequence>
+
+Print how many times the sequence must be played to get back to the solved
+state, then the cycles of the pieces it moves, each cubicle moving to the
+next one. `+` and `-` follow cycles whose corners end up twisted clockwise
+or counterclockwise, `+` edge cycles whose edges end up flipped.
+
+Options:
+  --strict           Only read face turns written R, R' or R2";
+
+const PATTERN_USAGE: &str = "Usage: rubik pattern [options] [name]
+
+Print a shortest sequence leading to the pattern, or the names of the
+patterns and the lengths of their sequences without a name.
+
+Options:
+  --format <format>  Draw the pattern instead: text, svg or isometric, as
+                     for `rubik show`
+  --color <renderer> auto (default), letters, blocks, ansi, ansi256 or
+                     truecolor, for the text format";
+
+const TABLES_USAGE: &str = "Usage: rubik tables <file>
+
+Write all the pruning tables to <file>, for the WebAssembly build to fetch
+instead of generating them. They are read from the pruning_tables folder,
+or generated there first.";
+
+const STATS_USAGE: &str = "Usage: rubik stats [options]
+
+Solve random states one after the other with the two-phase algorithm, and
+print the number of solutions of each length, the mean and median length,
+and the time and number of search nodes per solve. The same seed always
+gives the same states.
+
+Options:
+  --count <n>        Number of states (default 1000)
+  --seed <n>         Seed of the random states (default 1)
+  --max-depth <n>    Longest solution to look for (default 26)
+  --timeout <secs>   Give up on a state after this many seconds
+  --format <format>  text (default) or json";
+
+/// Why a command failed, which decides the exit code.
+enum Error {
+    /// Something is wrong with the command line.
+    Usage(String),
+    /// The command line is right, but the command could not do its job.
+    Failure(String),
+}
+
+/// Arguments of a command, split into options and positional arguments.
+struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    switches: Vec<String>,
+}
+
+impl Args {
+    /// `with_value` lists the options taking a value, `switches` the ones
+    /// which do not.
+    fn parse(args: &[String], with_value: &[&str], switches: &[&str]) -> Result<Self, Error> {
+        let mut parsed = Self {
+            positional: Vec::new(),
+            options: HashMap::new(),
+            switches: Vec::new(),
+        };
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if with_value.contains(&arg.as_str()) {
+                let value = args.next().ok_or_else(|| Error::Usage(format!("{} needs a value", arg)))?;
+                parsed.options.insert(arg.clone(), value.clone());
+            } else if switches.contains(&arg.as_str()) {
+                parsed.switches.push(arg.clone());
+            } else if arg.starts_with("--") {
+                return Err(Error::Usage(format!("unknown option {}", arg)));
+            } else {
+                parsed.positional.push(arg.clone());
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn switch(&self, name: &str) -> bool {
+        self.switches.iter().any(|x| x == name)
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|x| x.as_str())
+    }
+
+    /// Value of an option, or `default` if it is not given.
+    fn number<T: ::std::str::FromStr>(&self, name: &str, default: T) -> Result<T, Error> {
+        match self.option(name) {
+            Some(value) => value.parse().map_err(|_| Error::Usage(format!("invalid value for {}: {}", name, value))),
+            None => Ok(default),
+        }
+    }
+
+    /// `--format`, which must be one of `allowed`, defaulting to text.
+    fn format(&self, allowed: &[Format]) -> Result<Format, Error> {
+        let name = self.option("--format").unwrap_or("text");
+
+        match Format::from_str(name) {
+            Some(format) if allowed.contains(&format) => Ok(format),
+            _ => Err(Error::Usage(format!("unknown format {}", name))),
+        }
+    }
+
+    /// `--color`, the renderer of text drawings.
+    fn renderer(&self) -> Result<Box<Renderer>, Error> {
+        let name = self.option("--color").unwrap_or("auto");
+
+        render::from_name(name).ok_or_else(|| Error::Usage(format!("unknown renderer {}", name)))
+    }
+
+    /// `--timeout`, in seconds.
+    fn timeout(&self) -> Result<Option<Duration>, Error> {
+        match self.option("--timeout") {
+            Some(_) => {
+                let seconds: f64 = self.number("--timeout", 0.0)?;
+                Ok(Some(Duration::from_millis((seconds * 1000.0) as u64)))
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// `--strict`, the notation of sequences.
+    fn notation(&self) -> Notation {
+        if self.switch("--strict") { Notation::Strict } else { Notation::Lenient }
+    }
+
+    /// All the positional arguments, as one sequence of moves.
+    fn sequence(&self) -> Result<Vec<(UserMove, usize)>, Error> {
+        if self.positional.is_empty() {
+            return Err(Error::Usage("missing move sequence".to_string()));
+        }
+        UserMove::sequence_from_notation(&self.positional.join(" "), self.notation())
+            .map_err(|_| Error::Usage(format!("invalid move sequence: {}", self.positional.join(" "))))
+    }
+}
+
+/// Run the command line `args`, without the program name, and return the
+/// exit code.
+pub fn run(args: &[String]) -> i32 {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => {
+            eprintln!("{}", USAGE);
+            return 2;
+        },
+    };
+    let (run, usage): (fn(&Args) -> Result<(), Error>, &str) = match command {
+        "solve" => (solve, SOLVE_USAGE),
+        "batch" => (batch, BATCH_USAGE),
+        "scramble" => (scramble, SCRAMBLE_USAGE),
+        "show" => (show, SHOW_USAGE),
+        "play" => (play, PLAY_USAGE),
+        "verify" => (verify, VERIFY_USAGE),
+        "invert" => (invert, INVERT_USAGE),
+        "simplify" => (simplify, SIMPLIFY_USAGE),
+        "cycles" => (cycles, CYCLES_USAGE),
+        "pattern" => (pattern, PATTERN_USAGE),
+        "tables" => (tables, TABLES_USAGE),
+        "stats" => (stats, STATS_USAGE),
+        "--help" | "-h" | "help" => {
+            println!("{}", USAGE);
+            return 0;
+        },
+        _ => {
+            eprintln!("rubik: unknown command {}\n\n{}", command, USAGE);
+            return 2;
+        },
+    };
+    if args.iter().any(|x| x == "--help" || x == "-h") {
+        println!("{}", usage);
+        return 0;
+    }
+    if let Err(e) = ColorScheme::try_from_env() {
+        eprintln!("rubik: {}", e);
+        return 2;
+    }
+
+    let (with_value, switches): (&[&str], &[&str]) = match command {
+        "solve" => (&["--facelets", "--method", "--max-depth", "--timeout", "--format", "--color"], &["--strict"]),
+        "batch" => (&["--threads", "--max-depth", "--timeout", "--format"], &["--strict"]),
+        "scramble" => (&["--seed", "--length"], &[]),
+        "show" => (&["--format", "--next", "--color"], &["--strict"]),
+        "play" => (&["--delay", "--color"], &["--step", "--strict"]),
+        "invert" | "simplify" | "cycles" => (&[], &["--strict"]),
+        "pattern" => (&["--format", "--color"], &[]),
+        "stats" => (&["--count", "--seed", "--max-depth", "--timeout", "--format"], &[]),
+        _ => (&[], &[]),
+    };
+    let result = Args::parse(args, with_value, switches).and_then(|args| run(&args));
+    match result {
+        Ok(()) => 0,
+        Err(Error::Failure(message)) => {
+            eprintln!("rubik: {}", message);
+            1
+        },
+        Err(Error::Usage(message)) => {
+            eprintln!("rubik: {}\n\n{}", message, usage);
+            2
+        },
+    }
+}
+
+/// A scramble, or a facelet string if the line has 54 letters. As moves need
+/// no spaces between them, facelets with face letters as colors could read
+/// as a scramble too, so facelets come first.
+pub fn read_cube(line: &str, scheme: &ColorScheme, notation: Notation) -> Result<Cube, String> {
+    let facelets = if line.chars().filter(|c| !c.is_whitespace()).count() == 54 {
+        Some(Cube::from_facelets(line, scheme))
+    } else {
+        None
+    };
+
+    match (facelets, UserMove::sequence_from_notation(line, notation)) {
+        (Some(Ok(cube)), _) => Ok(cube),
+        (_, Ok(sequence)) => Ok(Cube::from_shuffle_sequence(sequence)),
+        (Some(Err(e)), Err(())) => Err(e.to_string()),
+        (None, Err(())) => Err("invalid move sequence".to_string()),
+    }
+}
+
+fn cube_from_args(args: &Args) -> Result<Cube, Error> {
+    match args.option("--facelets") {
+        Some(facelets) => {
+            if !args.positional.is_empty() {
+                return Err(Error::Usage("both a scramble and --facelets given".to_string()));
+            }
+            Cube::from_facelets(facelets, &ColorScheme::from_env()).map_err(|e| Error::Failure(e.to_string()))
+        },
+        None => Ok(Cube::from_shuffle_sequence(args.sequence()?)),
+    }
+}
+
+fn solve(args: &Args) -> Result<(), Error> {
+    let cube = cube_from_args(args)?;
+    let format = args.format(&[Format::Text, Format::Moves, Format::Json, Format::Csv])?;
+    let method = args.option("--method").unwrap_or("two-phase");
+    if method != "two-phase" && (args.option("--max-depth").is_some() || args.option("--timeout").is_some()) {
+        return Err(Error::Usage("--max-depth and --timeout only apply to two-phase".to_string()));
+    }
+
+    let input = args.option("--facelets").map(String::from).unwrap_or_else(|| args.positional.join(" "));
+    let mut report = Report::new(input, method);
+    let start = Instant::now();
+    let mut cases = Vec::new();
+    match method {
+        "two-phase" => {
+            let max_depth = args.number("--max-depth", 26)?;
+            let timeout = args.timeout()?;
+            let mut coordinate = Coordinate::from_cube(&cube);
+            report.tables = coordinate.init_pruning().status().to_string();
+            report.tables_time = start.elapsed();
+            let mut solver = Solver::new(&coordinate).max_depth(max_depth);
+            if let Some(timeout) = timeout {
+                solver = solver.timeout(timeout);
+            }
+
+            report.solve_two_phase(&cube, &solver);
+        },
+        "thistlethwaite" => {
+            let solver = Thistlethwaite::new();
+            report.tables_time = start.elapsed();
+
+            let start = Instant::now();
+            match solver.solve_phases(&cube) {
+                Some(phases) => {
+                    report.solution = UserMove::simplify_sequence(&phases.concat());
+                    report.phases = numbered_phases(phases);
+                },
+                None => report.error = Some("no solution found".to_string()),
+            }
+            report.solve_time = start.elapsed();
+        },
+        "cfop" => {
+            let solver = CfopSolver::new();
+            report.tables_time = start.elapsed();
+
+            let start = Instant::now();
+            match solver.solve(&cube) {
+                Some(steps) => for step in steps {
+                    report.solution.extend(step.moves.iter().cloned());
+                    report.phases.push((step.stage.to_string(), step.moves));
+                    cases.push(step.case);
+                },
+                None => report.error = Some("no solution found".to_string()),
+            }
+            report.solve_time = start.elapsed();
+        },
+        _ => return Err(Error::Usage(format!("unknown method {}", method))),
+    }
+
+    match format {
+        Format::Text if report.error.is_none() => {
+            print!("{}", cube.render(&*args.renderer()?));
+            if method == "cfop" {
+                for (&(ref stage, ref moves), case) in report.phases.iter().zip(cases.iter()) {
+                    println!("{:<6} {:<12} {}", stage, case, UserMove::sequence_to_string(moves));
+                }
+            } else {
+                println!("{}", UserMove::sequence_to_string(&report.solution));
+            }
+        },
+        Format::Moves if report.error.is_none() => println!("{}", UserMove::sequence_to_string(&report.solution)),
+        Format::Json => println!("{}", report.json()),
+        Format::Csv => {
+            println!("{}", CSV_HEADER);
+            println!("{}", report.csv());
+        },
+        _ => {},
+    }
+    match report.error {
+        Some(error) => Err(Error::Failure(error)),
+        None => Ok(()),
+    }
+}
+
+fn batch(args: &Args) -> Result<(), Error> {
+    let start = Instant::now();
+    let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+    let tables = coordinate.init_pruning();
+    let batch = Batch {
+        threads: args.number("--threads", 1)?,
+        max_depth: args.number("--max-depth", 26)?,
+        timeout: args.timeout()?,
+        scheme: ColorScheme::from_env(),
+        notation: args.notation(),
+        format: args.format(&[Format::Text, Format::Json, Format::Csv])?,
+        tables: tables,
+        tables_time: start.elapsed(),
+    };
+    let coordinate = Arc::new(coordinate);
+
+    let stdout = io::stdout();
+    let failures = match args.positional.len() {
+        0 => {
+            let stdin = io::stdin();
+            let input = stdin.lock();
+            batch.run(coordinate, input, &mut stdout.lock())
+        },
+        1 => {
+            let file = File::open(&args.positional[0])
+                .map_err(|e| Error::Failure(format!("{}: {}", args.positional[0], e)))?;
+            batch.run(coordinate, BufReader::new(file), &mut stdout.lock())
+        },
+        _ => return Err(Error::Usage("more than one input file".to_string())),
+    }.map_err(|e| Error::Failure(e.to_string()))?;
+
+    if failures > 0 {
+        return Err(Error::Failure(format!("{} lines failed", failures)));
+    }
+    Ok(())
+}
+
+fn scramble(args: &Args) -> Result<(), Error> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let default_seed = now.as_secs() ^ u64::from(now.subsec_nanos());
+    let seed = args.number("--seed", default_seed)?;
+    let length = args.number("--length", 25)?;
+
+    println!("{}", UserMove::sequence_to_string(&UserMove::random_sequence(length, seed)));
+    Ok(())
+}
+
+fn show(args: &Args) -> Result<(), Error> {
+    let cube = Cube::from_shuffle_sequence(args.sequence()?);
+    let mut svg = Svg::new().scheme(ColorScheme::from_env());
+    if let Some(next) = args.option("--next") {
+        match UserMove::sequence_from_notation(next, Notation::Strict) {
+            Ok(ref moves) if moves.len() == 1 => svg = svg.next_move(moves[0].0),
+            _ => return Err(Error::Usage(format!("invalid move for --next: {}", next))),
+        }
+    }
+
+    match args.option("--format") {
+        None | Some("text") => print!("{}", cube.render(&*args.renderer()?)),
+        Some("svg") => print!("{}", svg.net(&cube)),
+        Some("isometric") => print!("{}", svg.isometric(&cube)),
+        Some(format) => return Err(Error::Usage(format!("unknown format {}", format))),
+    }
+    Ok(())
+}
+
+fn play(args: &Args) -> Result<(), Error> {
+    let scramble = args.sequence()?;
+    let delay = args.number("--delay", 500)?;
+    let playback = Playback::new()
+        .delay(Duration::from_millis(delay))
+        .step(args.switch("--step"));
+
+    let cube = Cube::from_shuffle_sequence(scramble.clone());
+    let mut coordinate = Coordinate::from_cube(&cube);
+    coordinate.init_pruning();
+    let solution = Solver::new(&coordinate).solve(&cube)
+        .ok_or_else(|| Error::Failure("no solution found".to_string()))?;
+
+    let stdout = io::stdout();
+    playback.play(&*args.renderer()?, &scramble, &solution, &mut stdout.lock())
+        .map_err(|e| Error::Failure(e.to_string()))
+}
+
+fn verify(args: &Args) -> Result<(), Error> {
+    if args.positional.is_empty() {
+        return Err(Error::Usage("missing sticker colors".to_string()));
+    }
+    Cube::from_facelets(&args.positional.join(""), &ColorScheme::from_env())
+        .map_err(|e| Error::Failure(e.to_string()))?;
+    println!("solvable");
+    Ok(())
+}
+
+fn invert(args: &Args) -> Result<(), Error> {
+    println!("{}", UserMove::sequence_to_string(&UserMove::invert_sequence(&args.sequence()?)));
+    Ok(())
+}
+
+fn simplify(args: &Args) -> Result<(), Error> {
+    println!("{}", UserMove::sequence_to_string(&UserMove::simplify_sequence(&args.sequence()?)));
+    Ok(())
+}
+
+fn cycles(args: &Args) -> Result<(), Error> {
+    let cube = Cube::from_shuffle_sequence(args.sequence()?);
+
+    println!("order: {}", cube.order());
+    println!("cycles: {}", cube.cycle_decomposition().to_string());
+    Ok(())
+}
+
+fn pattern(args: &Args) -> Result<(), Error> {
+    let pattern = match args.positional.len() {
+        0 => {
+            for pattern in PATTERNS.iter() {
+                println!("{} ({})", pattern.name, pattern.sequence().len());
+            }
+            return Ok(());
+        },
+        1 => Pattern::find(&args.positional[0])
+            .ok_or_else(|| Error::Usage(format!("unknown pattern {}", args.positional[0])))?,
+        _ => return Err(Error::Usage("expected one pattern".to_string())),
+    };
+    let cube = pattern.cube();
+    let svg = Svg::new().scheme(ColorScheme::from_env());
+
+    match args.option("--format") {
+        None => println!("{}", pattern.sequence),
+        Some("text") => print!("{}", cube.render(&*args.renderer()?)),
+        Some("svg") => print!("{}", svg.net(&cube)),
+        Some("isometric") => print!("{}", svg.isometric(&cube)),
+        Some(format) => return Err(Error::Usage(format!("unknown format {}", format))),
+    }
+    Ok(())
+}
+
+fn tables(args: &Args) -> Result<(), Error> {
+    if args.positional.len() != 1 {
+        return Err(Error::Usage("expected one file".to_string()));
+    }
+    let path = &args.positional[0];
+    let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+    let mut blob = Blob::new();
+
+    coordinate.init_pruning();
+    coordinate.save_tables(&mut blob);
+    File::create(path)
+        .and_then(|mut file| file.write_all(&blob.to_bytes()))
+        .map_err(|e| Error::Failure(format!("{}: {}", path, e)))
+}
+
+fn stats(args: &Args) -> Result<(), Error> {
+    if !args.positional.is_empty() {
+        return Err(Error::Usage("unexpected arguments".to_string()));
+    }
+    let format = args.format(&[Format::Text, Format::Json])?;
+    let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+    coordinate.init_pruning();
+    let mut solver = Solver::new(&coordinate).max_depth(args.number("--max-depth", 26)?);
+    if let Some(timeout) = args.timeout()? {
+        solver = solver.timeout(timeout);
+    }
+
+    let statistics = Statistics::run(&solver, args.number("--count", 1000)?, args.number("--seed", 1)?);
+    match format {
+        Format::Json => println!("{}", statistics.json()),
+        _ => println!("{}", statistics.text()),
+    }
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use coordinate::TableLoad;
+use cube::Cube;
+use cube::color::ColorScheme;
+use move_::Notation;
+use move_::UserMove;
+use solver::Solver;
+
+use super::read_cube;
+
+/// How the results of solves are written.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    /// For humans.
+    Text,
+    /// The solution alone.
+    Moves,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "text" => Some(Format::Text),
+            "moves" => Some(Format::Moves),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+pub const CSV_HEADER: &str = "input,method,solution,htm,qtm,phases,tables,tables_ms,solve_ms,error";
+
+/// Everything known about one solve, for the machine readable formats.
+pub struct Report {
+    pub input: String,
+    pub method: String,
+    /// Empty if the solve failed.
+    pub solution: Vec<(UserMove, usize)>,
+    /// Name and moves of each phase, which may be simplified once put
+    /// together into the solution.
+    pub phases: Vec<(String, Vec<(UserMove, usize)>)>,
+    /// Status of `TableLoad`, or `built` when the tables of the method are
+    /// built in memory.
+    pub tables: String,
+    pub tables_time: Duration,
+    pub solve_time: Duration,
+    pub error: Option<String>,
+}
+
+/// Name the phases `phase 1`, `phase 2` and so on.
+pub fn numbered_phases(phases: Vec<Vec<(UserMove, usize)>>) -> Vec<(String, Vec<(UserMove, usize)>)> {
+    phases.into_iter().enumerate().map(|(i, moves)| (format!("phase {}", i + 1), moves)).collect()
+}
+
+pub fn milliseconds(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1000.0 + f64::from(duration.subsec_nanos()) / 1_000_000.0
+}
+
+/// `s` as a JSON string, quotes included.
+pub fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+impl Report {
+    /// A report of solving `input` with `method`, before the solve, with
+    /// no tables to load.
+    pub fn new(input: String, method: &str) -> Self {
+        Self {
+            input: input,
+            method: method.to_string(),
+            solution: Vec::new(),
+            phases: Vec::new(),
+            tables: "built".to_string(),
+            tables_time: Duration::from_secs(0),
+            solve_time: Duration::from_secs(0),
+            error: None,
+        }
+    }
+
+    /// Solve `input`, a scramble in `notation` or a facelet string, with the
+    /// two-phase `solver`, whose tables were loaded as told by `tables`.
+    pub fn two_phase(input: String, solver: &Solver, scheme: &ColorScheme, notation: Notation, tables: TableLoad,
+                     tables_time: Duration) -> Self {
+        let mut report = Self::new(input, "two-phase");
+        report.tables = tables.status().to_string();
+        report.tables_time = tables_time;
+
+        match read_cube(&report.input, scheme, notation) {
+            Ok(cube) => report.solve_two_phase(&cube, solver),
+            Err(error) => report.error = Some(error),
+        }
+        report
+    }
+
+    /// Solve `cube` with the two-phase `solver`, and time it.
+    pub fn solve_two_phase(&mut self, cube: &Cube, solver: &Solver) {
+        let start = Instant::now();
+
+        match solver.search(cube).0 {
+            Ok(phases) => {
+                self.solution = phases.concat();
+                self.phases = numbered_phases(phases);
+            },
+            Err(failure) => self.error = Some(failure.to_string()),
+        }
+        self.solve_time = start.elapsed();
+    }
+
+    /// Length in the half turn and quarter turn metrics.
+    pub fn lengths(&self) -> (usize, usize) {
+        (self.solution.len(), UserMove::quarter_turns(&self.solution))
+    }
+
+    /// One JSON object, on one line.
+    pub fn json(&self) -> String {
+        let (htm, qtm) = self.lengths();
+        let phases: Vec<String> = self.phases.iter()
+            .map(|&(ref name, ref moves)| format!("{{\"name\":{},\"moves\":{},\"length\":{}}}",
+                                                   json_string(name),
+                                                   json_string(&UserMove::sequence_to_string(moves)),
+                                                   moves.len()))
+            .collect();
+        let mut json = format!("{{\"input\":{},\"method\":{}", json_string(&self.input), json_string(&self.method));
+
+        match self.error {
+            Some(ref error) => json.push_str(&format!(",\"error\":{}", json_string(error))),
+            None => json.push_str(&format!(",\"solution\":{},\"length\":{{\"htm\":{},\"qtm\":{}}},\"phases\":[{}]",
+                                           json_string(&UserMove::sequence_to_string(&self.solution)),
+                                           htm, qtm, phases.join(","))),
+        }
+        json.push_str(&format!(",\"tables\":{},\"timings\":{{\"tables_ms\":{:.3},\"solve_ms\":{:.3}}}}}",
+                               json_string(&self.tables),
+                               milliseconds(self.tables_time),
+                               milliseconds(self.solve_time)));
+        json
+    }
+
+    /// One row following `CSV_HEADER`, the phases being the lengths of each
+    /// phase joined with `+`.
+    pub fn csv(&self) -> String {
+        let (htm, qtm) = self.lengths();
+        let phases: Vec<String> = self.phases.iter().map(|phase| phase.1.len().to_string()).collect();
+
+        [
+            csv_field(&self.input),
+            csv_field(&self.method),
+            csv_field(&UserMove::sequence_to_string(&self.solution)),
+            htm.to_string(),
+            qtm.to_string(),
+            phases.join("+"),
+            csv_field(&self.tables),
+            format!("{:.3}", milliseconds(self.tables_time)),
+            format!("{:.3}", milliseconds(self.solve_time)),
+            csv_field(self.error.as_ref().map(|x| x.as_str()).unwrap_or("")),
+        ].join(",")
+    }
+}
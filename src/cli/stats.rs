@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use cube::Cube;
+use solver::Solver;
+
+use super::report::milliseconds;
+
+/// One solve of a random state.
+pub struct Sample {
+    /// `None` if no solution was found.
+    pub length: Option<usize>,
+    pub time: Duration,
+    pub nodes: u64,
+}
+
+/// Mean, median and maximum of some values.
+struct Summary {
+    mean: f64,
+    median: f64,
+    max: f64,
+}
+
+impl Summary {
+    fn new(mut values: Vec<f64>) -> Self {
+        if values.is_empty() {
+            return Self { mean: 0.0, median: 0.0, max: 0.0 };
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = values.len();
+
+        Self {
+            mean: values.iter().sum::<f64>() / n as f64,
+            median: if n % 2 == 1 { values[n / 2] } else { (values[n / 2 - 1] + values[n / 2]) / 2.0 },
+            max: values[n - 1],
+        }
+    }
+
+    fn json(&self) -> String {
+        format!("{{\"mean\":{:.3},\"median\":{:.3},\"max\":{:.3}}}", self.mean, self.median, self.max)
+    }
+}
+
+/// `count` random states, the same for the same `seed` from one release to
+/// the next: the seed of each of them is drawn by xorshift64* from `seed`,
+/// so that two seeds give unrelated states.
+pub fn states(count: usize, seed: u64) -> Vec<Cube> {
+    let mut state = seed ^ 0x9e37_79b9_7f4a_7c15;
+
+    (0..count)
+        .map(|_| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            Cube::random(state.wrapping_mul(0x2545_f491_4f6c_dd1d))
+        })
+        .collect()
+}
+
+/// Solves of the random `states`.
+pub struct Statistics {
+    pub seed: u64,
+    pub samples: Vec<Sample>,
+}
+
+impl Statistics {
+    pub fn run(solver: &Solver, count: usize, seed: u64) -> Self {
+        let samples = states(count, seed).iter()
+            .map(|cube| {
+                let start = Instant::now();
+                let (phases, nodes) = solver.search(cube);
+
+                Sample {
+                    length: phases.ok().map(|phases| phases.iter().map(|phase| phase.len()).sum()),
+                    time: start.elapsed(),
+                    nodes: nodes,
+                }
+            })
+            .collect();
+
+        Self { seed: seed, samples: samples }
+    }
+
+    /// Number of solutions of each length.
+    fn histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for length in self.samples.iter().filter_map(|sample| sample.length) {
+            *histogram.entry(length).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    fn unsolved(&self) -> usize {
+        self.samples.iter().filter(|sample| sample.length.is_none()).count()
+    }
+
+    /// Summaries of the lengths of the solutions found, and of the time in
+    /// milliseconds and nodes of all the solves.
+    fn summaries(&self) -> (Summary, Summary, Summary) {
+        (Summary::new(self.samples.iter().filter_map(|sample| sample.length).map(|length| length as f64).collect()),
+         Summary::new(self.samples.iter().map(|sample| milliseconds(sample.time)).collect()),
+         Summary::new(self.samples.iter().map(|sample| sample.nodes as f64).collect()))
+    }
+
+    pub fn text(&self) -> String {
+        let (length, time, nodes) = self.summaries();
+        let histogram = self.histogram();
+        let largest = histogram.values().cloned().max().unwrap_or(0);
+        let mut text = format!("states: {} from seed {}, {} unsolved\n", self.samples.len(), self.seed, self.unsolved());
+
+        text.push_str(&format!("length: mean {:.2}, median {}, max {}\n", length.mean, length.median, length.max));
+        for (length, &count) in &histogram {
+            let bar = (count * 50 + largest - 1) / largest;
+            text.push_str(&format!("  {:2} {:6} {}\n", length, count, "#".repeat(bar)));
+        }
+        text.push_str(&format!("time: mean {:.3} ms, median {:.3} ms, max {:.3} ms\n", time.mean, time.median, time.max));
+        text.push_str(&format!("nodes: mean {:.0}, median {:.0}, max {:.0}", nodes.mean, nodes.median, nodes.max));
+        text
+    }
+
+    /// One JSON object, on one line.
+    pub fn json(&self) -> String {
+        let (length, time, nodes) = self.summaries();
+        let histogram: Vec<String> = self.histogram().iter()
+            .map(|(length, count)| format!("\"{}\":{}", length, count))
+            .collect();
+
+        format!("{{\"states\":{},\"seed\":{},\"unsolved\":{},\"length\":{},\"histogram\":{{{}}},\"time_ms\":{},\"nodes\":{}}}",
+                self.samples.len(), self.seed, self.unsolved(), length.json(), histogram.join(","), time.json(), nodes.json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use cli::stats::Statistics;
+    use cli::stats::states;
+    use coordinate::Coordinate;
+    use cube::Cube;
+    use solver::Solver;
+
+    #[test]
+    fn seeds_give_unrelated_states() {
+        let (first, second) = (states(100, 1), states(100, 2));
+
+        assert!(first == states(100, 1));
+        assert!(first.iter().all(|cube| !second.contains(cube)));
+    }
+
+    #[test]
+    fn deterministic_json() {
+        let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+        coordinate.init_pruning();
+        let solver = Solver::new(&coordinate);
+        let json = || {
+            let mut statistics = Statistics::run(&solver, 10, 7);
+            for sample in &mut statistics.samples {
+                sample.time = Duration::from_secs(0);
+            }
+            statistics.json()
+        };
+
+        assert_eq!(json(), json());
+    }
+}
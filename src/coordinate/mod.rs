@@ -1,14 +1,15 @@
+pub mod store;
+
 use cube::Cube;
 use move_::Move;
 
-use std::fs;
-use std::fs::File;
-use std::io::Write;
-use std::io::Read;
-
 use bincode;
 use serde;
 
+#[cfg(not(target_arch = "wasm32"))]
+use self::store::Directory;
+use self::store::TableStore;
+
 const NB_MOVES: usize = 18;
 const NB_TWIST: usize = 2187;
 const NB_FLIP: usize = 2048;
@@ -21,8 +22,27 @@ const NB_SLICE: usize = 24;
 const NB_SLICE_TWIST_FLIP: usize = 495;
 const NB_PARITY: usize = 2;
 
+/// How many tables `Coordinate::init_pruning` read from the cache, and
+/// how many it had to generate.
+#[derive(Clone, Copy)]
+pub struct TableLoad {
+    pub read: usize,
+    pub generated: usize,
+}
+
+impl TableLoad {
+    /// `cached`, `generated`, or `partial` when some tables were missing
+    /// from the cache.
+    pub fn status(&self) -> &'static str {
+        match (self.read, self.generated) {
+            (_, 0) => "cached",
+            (0, _) => "generated",
+            _ => "partial",
+        }
+    }
+}
+
 pub struct Coordinate {
-    cache_folder_name: String,
     twist: u32,
     flip: u32,
     parity: u32,
@@ -49,7 +69,6 @@ pub struct Coordinate {
 impl Coordinate {
     pub fn from_cube(cube: &Cube) -> Self {
         Self {
-            cache_folder_name: String::from("pruning_tables"),
             twist: cube.twist(),
             flip: cube.flip(),
             parity: cube.corner_parity(),
@@ -76,207 +95,270 @@ impl Coordinate {
         }
     }
 
-    fn create_cache_dir(&self) {
-        match fs::create_dir(&self.cache_folder_name) {
-            Ok(_) => println!("cache \"folder pruning_tables\" created"),
-            Err(e) => println!("{:?}", e),
-        }
-    }
-
-    fn dump_to_file<T>(&self, arr: T, name: &str)
+    fn dump_table<T>(store: &mut TableStore, arr: T, name: &str)
         where T: serde::ser::Serialize {
-        let mut path = self.cache_folder_name.to_owned();
-        path.push_str("/");
-        path.push_str(name);
-
-        let file = File::create(&path);
-        match file {
-            Ok(mut file) => {
-                let encoded: Vec<u8> = bincode::serialize(&arr, bincode::Infinite).unwrap();
-                match file.write(&encoded[..]) {
-                    Ok(_) => { },
-                    Err (e) => println!("{:?}", e),
-                }
-                match file.metadata() {
-                    Ok(metadata) => {
-                        let mut perm = metadata.permissions();
-                        perm.set_readonly(true);
-                        fs::set_permissions(&path, perm).unwrap();
-                    },
-                    Err(e) => println!("{:?}", e),
-                }
-            },
-            Err(e) => println!("{:?}", e),
-        };
+        let encoded: Vec<u8> = bincode::serialize(&arr, bincode::Infinite).unwrap();
+
+        store.write(name, &encoded);
     }
 
-    fn read_cache_table<T>(&self, name: &str) -> Option<T>
+    fn read_table<T>(store: &mut TableStore, name: &str) -> Option<T>
         where for<'a> T: serde::Deserialize<'a> {
-        let mut path = self.cache_folder_name.to_owned();
-        path.push_str("/");
-        path.push_str(name);
-
-        let f = File::open(path);
-        match f {
-            Ok(mut f) => {
-                let mut buffer = Vec::new();
-                let _ = f.read_to_end(&mut buffer);
-                let decoded: T = bincode::deserialize(&buffer).unwrap();
-                return Some(decoded);
-            },
-            Err(e) => println!("{:?}", e),
-        }
-        None
+        store.read(name).and_then(|buffer| bincode::deserialize(&buffer).ok())
     }
 
-    pub fn init_pruning(&mut self) {
-        self.create_cache_dir();
+    /// Read the tables from the `pruning_tables` cache folder, or generate
+    /// and cache the missing ones.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn init_pruning(&mut self) -> TableLoad {
+        self.init_pruning_from(&mut Directory::new("pruning_tables"))
+    }
+
+    /// Read the tables from `store`, or generate the missing ones and write
+    /// them to it.
+    pub fn init_pruning_from(&mut self, store: &mut TableStore) -> TableLoad {
+        let mut load = TableLoad { read: 0, generated: 0 };
 
-        match self.read_cache_table::<Box<[[u32; NB_MOVES]]>>("twist_move") {
+        match Self::read_table::<Box<[[u32; NB_MOVES]]>>(store, "twist_move") {
             Some(a) => {
-                println!("reading twist_move from file");
+                eprintln!("reading twist_move from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_TWIST);
                 self.twist_move = a;
             },
             None => {
                 self.init_twist_move();
-                println!("dumping twist_move in cache file");
-                self.dump_to_file(&self.twist_move[..], "twist_move");
+                eprintln!("dumping twist_move in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.twist_move[..], "twist_move");
             },
         }
 
-        match self.read_cache_table::<Box<[[u32; NB_MOVES]]>>("flip_move") {
+        match Self::read_table::<Box<[[u32; NB_MOVES]]>>(store, "flip_move") {
             Some(a) => {
-                println!("reading flip_move from file");
+                eprintln!("reading flip_move from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_FLIP);
                 self.flip_move = a;
             },
             None => {
                 self.init_flip_move();
-                println!("dumping flip_move in cache file");
-                self.dump_to_file(&self.flip_move[..], "flip_move");
+                eprintln!("dumping flip_move in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.flip_move[..], "flip_move");
             },
         }
 
-        match self.read_cache_table::<Box<[[u32; NB_MOVES]]>>("fr_to_br_move") {
+        match Self::read_table::<Box<[[u32; NB_MOVES]]>>(store, "fr_to_br_move") {
             Some(a) => {
-                println!("reading fr_to_br_move from file");
+                eprintln!("reading fr_to_br_move from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_FR_TO_BR);
                 self.fr_to_br_move = a;
             },
             None => {
                 self.init_fr_to_br_move();
-                println!("dumping fr_to_br_move in cache file");
-                self.dump_to_file(&self.fr_to_br_move[..], "fr_to_br_move");
+                eprintln!("dumping fr_to_br_move in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.fr_to_br_move[..], "fr_to_br_move");
             },
         }
 
-        match self.read_cache_table::<Box<[[u32; NB_MOVES]]>>("urf_to_dlf_move") {
+        match Self::read_table::<Box<[[u32; NB_MOVES]]>>(store, "urf_to_dlf_move") {
             Some(a) => {
-                println!("reading urf_to_dlf_move from file");
+                eprintln!("reading urf_to_dlf_move from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_URF_TO_DLF);
                 self.urf_to_dlf_move = a;
             },
             None => {
                 self.init_urf_to_dlf_move();
-                println!("dumping urf_to_dlf_move in cache file");
-                self.dump_to_file(&self.urf_to_dlf_move[..], "urf_to_dlf_move");
+                eprintln!("dumping urf_to_dlf_move in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.urf_to_dlf_move[..], "urf_to_dlf_move");
             },
         }
 
-        match self.read_cache_table::<Box<[[u32; NB_MOVES]]>>("ur_to_ul_move") {
+        match Self::read_table::<Box<[[u32; NB_MOVES]]>>(store, "ur_to_ul_move") {
             Some(a) => {
-                println!("reading ur_to_ul_move from file");
+                eprintln!("reading ur_to_ul_move from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_UR_TO_UL);
                 self.ur_to_ul_move = a;
             },
             None => {
                 self.init_ur_to_ul_move();
-                println!("dumping ur_to_ul_move in cache file");
-                self.dump_to_file(&self.ur_to_ul_move[..], "ur_to_ul_move");
+                eprintln!("dumping ur_to_ul_move in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.ur_to_ul_move[..], "ur_to_ul_move");
             },
         }
 
-        match self.read_cache_table::<Box<[[u32; NB_MOVES]]>>("ub_to_df_move") {
+        match Self::read_table::<Box<[[u32; NB_MOVES]]>>(store, "ub_to_df_move") {
             Some(a) => {
-                println!("reading ub_to_df_move from file");
+                eprintln!("reading ub_to_df_move from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_UB_TO_DF);
                 self.ub_to_df_move = a;
             },
             None => {
                 self.init_ub_to_df_move();
-                println!("dumping ub_to_df_move in cache file");
-                self.dump_to_file(&self.ub_to_df_move[..], "ub_to_df_move");
+                eprintln!("dumping ub_to_df_move in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.ub_to_df_move[..], "ub_to_df_move");
             },
         }
 
-        match self.read_cache_table::<Box<[[u32; NB_MOVES]]>>("ur_to_df_move") {
+        match Self::read_table::<Box<[[u32; NB_MOVES]]>>(store, "ur_to_df_move") {
             Some(a) => {
-                println!("reading ur_to_df_move from file");
+                eprintln!("reading ur_to_df_move from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_UR_TO_DF);
                 self.ur_to_df_move = a;
             },
             None => {
                 self.init_ur_to_df_move();
-                println!("dumping ur_to_df_move in cache file");
-                self.dump_to_file(&self.ur_to_df_move[..], "ur_to_df_move");
+                eprintln!("dumping ur_to_df_move in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.ur_to_df_move[..], "ur_to_df_move");
             },
         }
 
         self.init_merge_ur_to_ul_and_ub_to_df();
         // self.dump_to_file(&self.merge_ur_to_ul_and_ub_to_df.iter().map(|x| &x[..]).collect::<Vec<&[i16]>>(), "merge_ur_to_ul_and_ub_to_df");
 
-        match self.read_cache_table::<Box<[i8]>>("urf_to_dlf_parity_prun") {
+        match Self::read_table::<Box<[i8]>>(store, "urf_to_dlf_parity_prun") {
             Some(a) => {
-                println!("reading urf_to_dlf_parity_prun from file");
+                eprintln!("reading urf_to_dlf_parity_prun from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_SLICE * NB_URF_TO_DLF * NB_PARITY / 2);
                 self.urf_to_dlf_parity_prun = a;
             },
             None => {
                 self.init_urf_to_dlf_parity_prun();
-                println!("dumping urf_to_dlf_parity_prun in cache file");
-                self.dump_to_file(&self.urf_to_dlf_parity_prun[..], "urf_to_dlf_parity_prun");
+                eprintln!("dumping urf_to_dlf_parity_prun in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.urf_to_dlf_parity_prun[..], "urf_to_dlf_parity_prun");
             },
         }
 
-        match self.read_cache_table::<Box<[i8]>>("ur_to_df_parity_prun") {
+        match Self::read_table::<Box<[i8]>>(store, "ur_to_df_parity_prun") {
             Some(a) => {
-                println!("reading ur_to_df_parity_prun from file");
+                eprintln!("reading ur_to_df_parity_prun from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_SLICE * NB_UR_TO_DF * NB_PARITY / 2);
                 self.ur_to_df_parity_prun = a;
             },
             None => {
                 self.init_ur_to_df_parity_prun();
-                println!("dumping ur_to_df_parity_prun in cache file");
-                self.dump_to_file(&self.ur_to_df_parity_prun[..], "ur_to_df_parity_prun");
+                eprintln!("dumping ur_to_df_parity_prun in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.ur_to_df_parity_prun[..], "ur_to_df_parity_prun");
             },
         }
 
-        match self.read_cache_table::<Box<[i8]>>("twist_prun") {
+        match Self::read_table::<Box<[i8]>>(store, "twist_prun") {
             Some(a) => {
-                println!("reading twist_prun from file");
+                eprintln!("reading twist_prun from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_SLICE_TWIST_FLIP * NB_TWIST / 2 + 1);
                 self.twist_prun = a;
             },
             None => {
                 self.init_twist_prun();
-                println!("dumping twist_prun in cache file");
-                self.dump_to_file(&self.twist_prun[..], "twist_prun");
+                eprintln!("dumping twist_prun in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.twist_prun[..], "twist_prun");
             },
         }
 
-        match self.read_cache_table::<Box<[i8]>>("flip_prun") {
+        match Self::read_table::<Box<[i8]>>(store, "flip_prun") {
             Some(a) => {
-                println!("reading flip_prun from file");
+                eprintln!("reading flip_prun from cache");
+                load.read += 1;
                 assert_eq!(a.len(), NB_SLICE_TWIST_FLIP * NB_FLIP / 2 + 1);
                 self.flip_prun = a;
             },
             None => {
                 self.init_flip_prun();
-                println!("dumping flip_prun in cache file");
-                self.dump_to_file(&self.flip_prun[..], "flip_prun");
+                eprintln!("dumping flip_prun in cache");
+                load.generated += 1;
+                Self::dump_table(store, &self.flip_prun[..], "flip_prun");
             },
         }
+        load
+    }
+
+    /// Write every table read by `init_pruning_from` to `store`.
+    pub fn save_tables(&self, store: &mut TableStore) {
+        Self::dump_table(store, &self.twist_move[..], "twist_move");
+        Self::dump_table(store, &self.flip_move[..], "flip_move");
+        Self::dump_table(store, &self.fr_to_br_move[..], "fr_to_br_move");
+        Self::dump_table(store, &self.urf_to_dlf_move[..], "urf_to_dlf_move");
+        Self::dump_table(store, &self.ur_to_ul_move[..], "ur_to_ul_move");
+        Self::dump_table(store, &self.ub_to_df_move[..], "ub_to_df_move");
+        Self::dump_table(store, &self.ur_to_df_move[..], "ur_to_df_move");
+        Self::dump_table(store, &self.urf_to_dlf_parity_prun[..], "urf_to_dlf_parity_prun");
+        Self::dump_table(store, &self.ur_to_df_parity_prun[..], "ur_to_df_parity_prun");
+        Self::dump_table(store, &self.twist_prun[..], "twist_prun");
+        Self::dump_table(store, &self.flip_prun[..], "flip_prun");
+    }
+
+    pub fn twist_move(&self, twist: u32, m: usize) -> u32 {
+        self.twist_move[twist as usize][m]
+    }
+
+    pub fn flip_move(&self, flip: u32, m: usize) -> u32 {
+        self.flip_move[flip as usize][m]
+    }
+
+    pub fn parity_move(&self, parity: u32, m: usize) -> u32 {
+        self.parity_move[parity as usize][m] as u32
+    }
+
+    pub fn fr_to_br_move(&self, fr_to_br: u32, m: usize) -> u32 {
+        self.fr_to_br_move[fr_to_br as usize][m]
+    }
+
+    pub fn urf_to_dlf_move(&self, urf_to_dlf: u32, m: usize) -> u32 {
+        self.urf_to_dlf_move[urf_to_dlf as usize][m]
+    }
+
+    pub fn ur_to_ul_move(&self, ur_to_ul: u32, m: usize) -> u32 {
+        self.ur_to_ul_move[ur_to_ul as usize][m]
+    }
+
+    pub fn ub_to_df_move(&self, ub_to_df: u32, m: usize) -> u32 {
+        self.ub_to_df_move[ub_to_df as usize][m]
+    }
+
+    pub fn ur_to_df_move(&self, ur_to_df: u32, m: usize) -> u32 {
+        self.ur_to_df_move[ur_to_df as usize][m]
+    }
+
+    /// Only meaningful once the cube is in phase 2, where both coordinates are below 336.
+    pub fn merge_ur_to_ul_and_ub_to_df(&self, ur_to_ul: u32, ub_to_df: u32) -> u32 {
+        self.merge_ur_to_ul_and_ub_to_df[ur_to_ul as usize][ub_to_df as usize] as u32
+    }
+
+    /// Lower bound of the number of moves needed to reach phase 2.
+    /// `slice` is the `fr_to_br` coordinate divided by 24.
+    pub fn phase1_distance(&self, twist: u32, flip: u32, slice: u32) -> i8 {
+        let twist_index = NB_SLICE_TWIST_FLIP * twist as usize + slice as usize;
+        let flip_index = NB_SLICE_TWIST_FLIP * flip as usize + slice as usize;
+
+        ::std::cmp::max(Self::prunning(&self.twist_prun[..], twist_index),
+                        Self::prunning(&self.flip_prun[..], flip_index))
+    }
+
+    /// Lower bound of the number of phase 2 moves needed to solve the cube.
+    /// `slice` is the `fr_to_br` coordinate, which is below 24 in phase 2.
+    pub fn phase2_distance(&self, urf_to_dlf: u32, ur_to_df: u32, slice: u32, parity: u32) -> i8 {
+        let corners_index = (NB_SLICE * urf_to_dlf as usize + slice as usize) * 2 + parity as usize;
+        let edges_index = (NB_SLICE * ur_to_df as usize + slice as usize) * 2 + parity as usize;
+
+        ::std::cmp::max(Self::prunning(&self.urf_to_dlf_parity_prun[..], corners_index),
+                        Self::prunning(&self.ur_to_df_parity_prun[..], edges_index))
     }
 
     fn init_twist_move(&mut self) {
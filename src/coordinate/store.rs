@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs::File;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::Read;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::Write;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+use bincode;
+
+/// Where `Coordinate::init_pruning_from` looks for the tables, and stores
+/// the ones it had to generate. Tables are named and serialized with
+/// bincode.
+pub trait TableStore {
+    fn read(&mut self, name: &str) -> Option<Vec<u8>>;
+    fn write(&mut self, name: &str, bytes: &[u8]);
+}
+
+/// One read-only file per table, in a directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Directory {
+    path: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Directory {
+    /// Create the directory if it does not exist yet.
+    pub fn new(path: &str) -> Self {
+        match fs::create_dir(path) {
+            Ok(_) => eprintln!("cache folder \"{}\" created", path),
+            Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => { },
+            Err(e) => eprintln!("{:?}", e),
+        }
+        Self { path: PathBuf::from(path) }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TableStore for Directory {
+    fn read(&mut self, name: &str) -> Option<Vec<u8>> {
+        match File::open(self.path.join(name)) {
+            Ok(mut file) => {
+                let mut buffer = Vec::new();
+                match file.read_to_end(&mut buffer) {
+                    Ok(_) => Some(buffer),
+                    Err(e) => {
+                        eprintln!("{:?}", e);
+                        None
+                    },
+                }
+            },
+            Err(e) => {
+                eprintln!("{:?}", e);
+                None
+            },
+        }
+    }
+
+    fn write(&mut self, name: &str, bytes: &[u8]) {
+        let path = self.path.join(name);
+
+        match File::create(&path) {
+            Ok(mut file) => {
+                if let Err(e) = file.write_all(bytes) {
+                    eprintln!("{:?}", e);
+                }
+                match file.metadata() {
+                    Ok(metadata) => {
+                        let mut perm = metadata.permissions();
+                        perm.set_readonly(true);
+                        if let Err(e) = fs::set_permissions(&path, perm) {
+                            eprintln!("{:?}", e);
+                        }
+                    },
+                    Err(e) => eprintln!("{:?}", e),
+                }
+            },
+            Err(e) => eprintln!("{:?}", e),
+        }
+    }
+}
+
+/// All the tables in memory, which can be saved to and loaded from a single
+/// binary blob, to be fetched by a browser for example.
+///
+/// An empty `Blob` makes `init_pruning_from` generate every table, and
+/// collects them.
+pub struct Blob {
+    tables: BTreeMap<String, Vec<u8>>,
+}
+
+impl Blob {
+    pub fn new() -> Self {
+        Self { tables: BTreeMap::new() }
+    }
+
+    /// `None` if `bytes` were not made by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        bincode::deserialize(bytes).ok().map(|tables| Self { tables: tables })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(&self.tables, bincode::Infinite).unwrap()
+    }
+}
+
+impl TableStore for Blob {
+    fn read(&mut self, name: &str) -> Option<Vec<u8>> {
+        self.tables.get(name).cloned()
+    }
+
+    fn write(&mut self, name: &str, bytes: &[u8]) {
+        self.tables.insert(name.to_string(), bytes.to_vec());
+    }
+}
@@ -0,0 +1,247 @@
+use std::env;
+
+use cube::face::Face;
+
+/// Color of the stickers of one face.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Color {
+    /// Letter used for this color in facelet strings.
+    pub letter: char,
+    pub rgb: (u8, u8, u8),
+    /// Closest of the 16 standard ANSI foreground colors, from 30 to 37 and
+    /// from 90 to 97.
+    pub ansi: u8,
+}
+
+pub const WHITE: Color = Color { letter: 'W', rgb: (255, 255, 255), ansi: 97 };
+pub const YELLOW: Color = Color { letter: 'Y', rgb: (255, 213, 0), ansi: 93 };
+pub const RED: Color = Color { letter: 'R', rgb: (196, 30, 58), ansi: 31 };
+pub const ORANGE: Color = Color { letter: 'O', rgb: (255, 88, 0), ansi: 91 };
+pub const GREEN: Color = Color { letter: 'G', rgb: (0, 155, 72), ansi: 32 };
+pub const BLUE: Color = Color { letter: 'B', rgb: (0, 70, 173), ansi: 34 };
+
+/// RGB values of the 16 standard ANSI colors, as xterm draws them.
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+    (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+    (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+    (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+/// Intensities of the 6x6x6 color cube of the 256 colors palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2) as u32;
+
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+impl Color {
+    /// A custom color, drawn with the closest ANSI color on 16 colors
+    /// terminals.
+    pub fn new(letter: char, r: u8, g: u8, b: u8) -> Self {
+        let index = (0..16).min_by_key(|&x| distance(ANSI_RGB[x], (r, g, b))).unwrap();
+
+        Self {
+            letter: letter.to_ascii_uppercase(),
+            rgb: (r, g, b),
+            ansi: if index < 8 { 30 + index as u8 } else { 82 + index as u8 },
+        }
+    }
+
+    /// Closest color of the 256 colors palette, from its 6x6x6 cube.
+    pub fn ansi256(&self) -> u8 {
+        let level = |x: u8| (0..6).min_by_key(|&y| (i32::from(CUBE_LEVELS[y]) - i32::from(x)).abs()).unwrap() as u8;
+        let (r, g, b) = self.rgb;
+
+        16 + 36 * level(r) + 6 * level(g) + level(b)
+    }
+
+    /// Whether black text is more readable than white text on this color.
+    pub fn is_light(&self) -> bool {
+        let (r, g, b) = self.rgb;
+
+        299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b) > 128_000
+    }
+
+    /// `#rrggbb` notation, for HTML and SVG.
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.rgb.0, self.rgb.1, self.rgb.2)
+    }
+}
+
+/// Which color each face of the cube has.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct ColorScheme {
+    /// Indexed by `Face`.
+    pub colors: [Color; 6],
+}
+
+/// Two faces of a scheme with the same letter, which facelet strings could
+/// not tell apart.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct DuplicateLetter(pub char);
+
+impl ToString for DuplicateLetter {
+    fn to_string(&self) -> String {
+        format!("two faces have the letter '{}'", self.0)
+    }
+}
+
+/// One of the six standard colors, from its letter, or a custom one written
+/// as its letter then `#rrggbb`.
+fn parse_color(s: &str) -> Option<Color> {
+    let mut chars = s.chars();
+    let letter = chars.next()?.to_ascii_uppercase();
+    let hex = chars.as_str();
+
+    if hex.is_empty() {
+        return [WHITE, YELLOW, RED, ORANGE, GREEN, BLUE].iter().find(|color| color.letter == letter).cloned();
+    }
+    if !letter.is_ascii_alphabetic() || hex.len() != 7 || !hex.starts_with('#') {
+        return None;
+    }
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|x| u8::from_str_radix(x, 16).ok());
+    Some(Color::new(letter, channel(1)?, channel(3)?, channel(5)?))
+}
+
+impl ColorScheme {
+    /// White opposite yellow, red opposite orange and green opposite blue,
+    /// with white on top and green in front.
+    pub fn western() -> Self {
+        Self::unchecked(WHITE, YELLOW, GREEN, BLUE, ORANGE, RED)
+    }
+
+    /// White opposite blue, red opposite orange and green opposite yellow,
+    /// with white on top and green in front.
+    pub fn japanese() -> Self {
+        Self::unchecked(WHITE, BLUE, GREEN, YELLOW, ORANGE, RED)
+    }
+
+    /// A custom scheme, from the colors of the U, D, F, B, L and R faces,
+    /// whose letters must all differ.
+    pub fn new(up: Color, down: Color, front: Color, back: Color, left: Color, right: Color)
+               -> Result<Self, DuplicateLetter> {
+        Self::unchecked(up, down, front, back, left, right).checked()
+    }
+
+    fn unchecked(up: Color, down: Color, front: Color, back: Color, left: Color, right: Color) -> Self {
+        let mut colors = [up; 6];
+
+        colors[Face::U as usize] = up;
+        colors[Face::D as usize] = down;
+        colors[Face::F as usize] = front;
+        colors[Face::B as usize] = back;
+        colors[Face::L as usize] = left;
+        colors[Face::R as usize] = right;
+        Self { colors: colors }
+    }
+
+    /// Replace the color of one face, whose letter must differ from the ones
+    /// of the other faces.
+    pub fn with(mut self, face: Face, color: Color) -> Result<Self, DuplicateLetter> {
+        self.colors[face as usize] = color;
+        self.checked()
+    }
+
+    fn checked(self) -> Result<Self, DuplicateLetter> {
+        for i in 0..6 {
+            if self.colors[..i].iter().any(|color| color.letter == self.colors[i].letter) {
+                return Err(DuplicateLetter(self.colors[i].letter));
+            }
+        }
+        Ok(self)
+    }
+
+    /// `western`, `japanese`, or the colors of the U, R, F, D, L and B faces,
+    /// in the order of facelet strings: six letters of the standard colors,
+    /// such as `WRGYOB`, or six colors separated by commas, each a letter
+    /// optionally followed by `#rrggbb`, such as `W,R,G,Y,O,P#800080`.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "western" => return Ok(Self::western()),
+            "japanese" => return Ok(Self::japanese()),
+            _ => { },
+        }
+        let colors: Option<Vec<Color>> = if name.contains(',') {
+            name.split(',').map(|x| parse_color(x.trim())).collect()
+        } else {
+            name.chars().map(|c| parse_color(&c.to_string())).collect()
+        };
+
+        match colors {
+            Some(ref colors) if colors.len() == 6 => {
+                Self::new(colors[0], colors[3], colors[2], colors[5], colors[4], colors[1]).map_err(|e| e.to_string())
+            },
+            _ => Err(format!("unknown color scheme {}", name)),
+        }
+    }
+
+    /// The scheme named in the `RUBIK_COLORS` environment variable, as read
+    /// by `from_name`, `western` if it is not set.
+    pub fn try_from_env() -> Result<Self, String> {
+        match env::var("RUBIK_COLORS") {
+            Ok(value) => Self::from_name(&value).map_err(|e| format!("RUBIK_COLORS: {}", e)),
+            Err(_) => Ok(Self::western()),
+        }
+    }
+
+    /// Same as `try_from_env`, `western` if `RUBIK_COLORS` is invalid.
+    pub fn from_env() -> Self {
+        Self::try_from_env().unwrap_or_default()
+    }
+
+    pub fn color(&self, face: Face) -> Color {
+        self.colors[face as usize]
+    }
+
+    /// The face whose color is written `letter`, ignoring case.
+    pub fn face(&self, letter: char) -> Option<Face> {
+        let letter = letter.to_ascii_uppercase();
+
+        [Face::F, Face::B, Face::U, Face::D, Face::L, Face::R].iter()
+            .find(|&&face| self.color(face).letter == letter)
+            .cloned()
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::western()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cube::color::BLUE;
+    use cube::color::ColorScheme;
+    use cube::color::GREEN;
+    use cube::color::ORANGE;
+    use cube::color::RED;
+    use cube::color::WHITE;
+    use cube::color::YELLOW;
+    use cube::face::Face;
+
+    #[test]
+    fn distinct_letters() {
+        assert!(ColorScheme::new(WHITE, WHITE, GREEN, BLUE, ORANGE, RED).is_err());
+        assert!(ColorScheme::western().with(Face::U, YELLOW).is_err());
+        assert!(ColorScheme::western().with(Face::D, BLUE).is_err());
+        assert!(ColorScheme::from_name("W,R,G,Y,O,R#800080").is_err());
+    }
+
+    #[test]
+    fn from_name() {
+        assert!(ColorScheme::from_name("WRGYOB").unwrap() == ColorScheme::western());
+        assert!(ColorScheme::from_name("w, r, g, b, o, y").unwrap() == ColorScheme::japanese());
+
+        let scheme = ColorScheme::from_name("W,R,G,Y,O,P#800080").unwrap();
+        assert_eq!(scheme.color(Face::B).rgb, (0x80, 0, 0x80));
+        assert!(scheme.face('p') == Some(Face::B));
+
+        assert!(ColorScheme::from_name("WRGYO").is_err());
+        assert!(ColorScheme::from_name("WRGYOX").is_err());
+        assert!(ColorScheme::from_name("W,R,G,Y,O,P#80008").is_err());
+    }
+}
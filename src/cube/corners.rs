@@ -2,6 +2,14 @@ use cube::face::Face;
 // use cube::CubeMove;
 use move_::Move;
 use move_::Move_;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serialization::deserialize_name;
+use serialization::serialize_name;
+
+const NAMES: [&str; 8] = ["URF", "UFL", "ULB", "UBR", "DFR", "DLF", "DBL", "DRB"];
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq)]
 pub enum Corner {
@@ -16,6 +24,21 @@ pub enum Corner {
 }
 
 impl Corner {
+    pub fn from_u(u: usize) -> Corner {
+        use self::Corner::*;
+        match u {
+            0 => URF,
+            1 => UFL,
+            2 => ULB,
+            3 => UBR,
+            4 => DFR,
+            5 => DLF,
+            6 => DBL,
+            7 => DRB,
+            _ => unreachable!("corner index {}", u),
+        }
+    }
+
     fn decompose(&self) -> (Face, Face, Face) {
         use self::Corner::*;
         match *self {
@@ -88,6 +111,18 @@ impl ToString for Corner {
     }
 }
 
+impl Serialize for Corner {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_name(serializer, &NAMES, usize::from(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Corner {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_name(deserializer, &NAMES).map(Corner::from_u)
+    }
+}
+
 impl From<Corner> for usize {
     fn from(c: Corner) -> Self {
         use self::Corner::*;
@@ -105,7 +140,7 @@ impl From<Corner> for usize {
     }
 }
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Corners {
     pub permutations: [Corner; 8],
     pub orientations: [u8; 8],
@@ -118,14 +153,24 @@ impl Corners {
 
     pub fn multiply(&mut self, m: Move) {
         let m = Move_::move_definition(m);
+
+        self.multiply_arrays(&m.corners_permutation, &m.corners_orientation);
+    }
+
+    /// Apply the corners of `other` on top of `self`.
+    pub fn multiply_by(&mut self, other: &Corners) {
+        self.multiply_arrays(&other.permutations, &other.orientations);
+    }
+
+    fn multiply_arrays(&mut self, permutations: &[Corner; 8], orientations: &[u8; 8]) {
         let mut new_corners = Self::new();
 
         for corner in usize::from(Corner::URF)..usize::from(Corner::DRB) + 1 {
-            let index = usize::from(m.corners_permutation[corner]);
+            let index = usize::from(permutations[corner]);
             new_corners.permutations[corner] = self.permutations[index];
 
             let ori_a: i8  = self.orientations[index] as i8;
-            let ori_b: i8  = m.corners_orientation[corner] as i8;
+            let ori_b: i8  = orientations[corner] as i8;
             let mut ori: i8 = 0;
 
             if ori_a < 3 && ori_b < 3 {
@@ -145,6 +190,19 @@ impl Corners {
         }
         *self = new_corners;
     }
+
+    pub fn inverse(&self) -> Self {
+        let mut inverse = Self::new();
+
+        for corner in usize::from(Corner::URF)..usize::from(Corner::DRB) + 1 {
+            inverse.permutations[usize::from(self.permutations[corner])] = Corner::from_u(corner);
+        }
+        for corner in usize::from(Corner::URF)..usize::from(Corner::DRB) + 1 {
+            let ori = self.orientations[usize::from(inverse.permutations[corner])];
+            inverse.orientations[corner] = (3 - ori % 3) % 3;
+        }
+        inverse
+    }
 }
 
 impl Default for Corners {
@@ -0,0 +1,156 @@
+use cube::Cube;
+use cube::corners::Corner;
+use cube::edges::Edge;
+
+/// Corners moving into each other, each to the next cubicle and the last
+/// one to the first.
+#[derive(Clone, PartialEq)]
+pub struct CornerCycle {
+    pub cubicles: Vec<Corner>,
+    /// Clockwise twist, in [0, 3), the corners have once back to their
+    /// cubicle.
+    pub twist: u8,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct EdgeCycle {
+    pub cubicles: Vec<Edge>,
+    /// 1 if the edges are flipped once back to their cubicle.
+    pub flip: u8,
+}
+
+/// The pieces moved or turned by a state, as cycles.
+#[derive(Clone, PartialEq)]
+pub struct Cycles {
+    pub corners: Vec<CornerCycle>,
+    pub edges: Vec<EdgeCycle>,
+}
+
+/// `(URF UFL ULB)`, followed by `+` for a clockwise twist and `-` for a
+/// counterclockwise one.
+impl ToString for CornerCycle {
+    fn to_string(&self) -> String {
+        let cubicles: Vec<String> = self.cubicles.iter().map(|c| c.to_string()).collect();
+        let twist = match self.twist {
+            1 => "+",
+            2 => "-",
+            _ => "",
+        };
+
+        format!("({}){}", cubicles.join(" "), twist)
+    }
+}
+
+/// `(UR UF UL)`, followed by `+` when flipped.
+impl ToString for EdgeCycle {
+    fn to_string(&self) -> String {
+        let cubicles: Vec<String> = self.cubicles.iter().map(|e| e.to_string()).collect();
+
+        format!("({}){}", cubicles.join(" "), if self.flip == 1 { "+" } else { "" })
+    }
+}
+
+/// Corner cycles then edge cycles, separated by spaces.
+impl ToString for Cycles {
+    fn to_string(&self) -> String {
+        let cycles: Vec<String> = self.corners.iter().map(|c| c.to_string())
+            .chain(self.edges.iter().map(|e| e.to_string()))
+            .collect();
+
+        cycles.join(" ")
+    }
+}
+
+/// Cycles of `permutations`, a piece at `permutations[i]` moving to `i`,
+/// with the sum of the `orientations` of their cubicles. Pieces left in
+/// place with no orientation are left out.
+fn cycles(permutations: &[usize], orientations: &[u8]) -> Vec<(Vec<usize>, u8)> {
+    let mut destinations = vec![0; permutations.len()];
+    for (i, &p) in permutations.iter().enumerate() {
+        destinations[p] = i;
+    }
+
+    let mut seen = vec![false; permutations.len()];
+    let mut cycles = Vec::new();
+    for start in 0..permutations.len() {
+        if seen[start] {
+            continue;
+        }
+        let (mut cycle, mut orientation, mut cubicle) = (Vec::new(), 0, start);
+        while !seen[cubicle] {
+            seen[cubicle] = true;
+            cycle.push(cubicle);
+            orientation += orientations[cubicle];
+            cubicle = destinations[cubicle];
+        }
+        if cycle.len() > 1 || orientation != 0 {
+            cycles.push((cycle, orientation));
+        }
+    }
+    cycles
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+impl Cube {
+    /// Cycles of the corners and edges moved or turned by the state, as if
+    /// the moves leading to it were played once more.
+    pub fn cycle_decomposition(&self) -> Cycles {
+        let corners: Vec<usize> = self.corners.permutations.iter().map(|&c| usize::from(c)).collect();
+        let edges: Vec<usize> = self.edges.permutations.iter().map(|&e| usize::from(e)).collect();
+
+        Cycles {
+            corners: cycles(&corners, &self.corners.orientations).into_iter()
+                .map(|(cycle, twist)| CornerCycle {
+                    cubicles: cycle.into_iter().map(Corner::from_u).collect(),
+                    twist: twist % 3,
+                })
+                .collect(),
+            edges: cycles(&edges, &self.edges.orientations).into_iter()
+                .map(|(cycle, flip)| EdgeCycle {
+                    cubicles: cycle.into_iter().map(Edge::from_u).collect(),
+                    flip: flip % 2,
+                })
+                .collect(),
+        }
+    }
+
+    /// How many times the moves leading to the state must be played to get
+    /// back to the solved state: 1 for the solved state itself.
+    pub fn order(&self) -> usize {
+        let cycles = self.cycle_decomposition();
+        let lengths = cycles.corners.iter().map(|c| c.cubicles.len() * if c.twist == 0 { 1 } else { 3 })
+            .chain(cycles.edges.iter().map(|e| e.cubicles.len() * if e.flip == 0 { 1 } else { 2 }));
+
+        lengths.fold(1, |order, length| order / gcd(order, length) * length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cube::Cube;
+    use move_::UserMove;
+
+    fn cube(sequence: &str) -> Cube {
+        Cube::from_shuffle_sequence(UserMove::sequence_from_str(sequence).unwrap())
+    }
+
+    #[test]
+    fn order() {
+        assert_eq!(Cube::new_default().order(), 1);
+        assert_eq!(cube("R").order(), 4);
+        assert_eq!(cube("R U").order(), 105);
+        assert_eq!(cube("R U R' U'").order(), 6);
+        assert_eq!(UserMove::sequence_order(&UserMove::sequence_from_str("R U").unwrap()), 105);
+    }
+
+    #[test]
+    fn cycle_decomposition() {
+        assert_eq!(Cube::new_default().cycle_decomposition().to_string(), "");
+        assert_eq!(cube("R").cycle_decomposition().to_string(), "(URF UBR DRB DFR) (UR BR DR FR)");
+        assert_eq!(cube("R U R' U' R' F R2 U' R' U' R U R' F'").cycle_decomposition().to_string(),
+                   "(URF UBR) (UR UL)");
+    }
+}
@@ -2,6 +2,14 @@ use cube::face::Face;
 // use cube::CubeMove;
 use move_::Move;
 use move_::Move_;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serialization::deserialize_name;
+use serialization::serialize_name;
+
+const NAMES: [&str; 12] = ["UR", "UF", "UL", "UB", "DR", "DF", "DL", "DB", "FR", "FL", "BL", "BR"];
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq)]
 pub enum Edge {
@@ -20,6 +28,25 @@ pub enum Edge {
 }
 
 impl Edge {
+    pub fn from_u(u: usize) -> Edge {
+        use self::Edge::*;
+        match u {
+            0 => UR,
+            1 => UF,
+            2 => UL,
+            3 => UB,
+            4 => DR,
+            5 => DF,
+            6 => DL,
+            7 => DB,
+            8 => FR,
+            9 => FL,
+            10 => BL,
+            11 => BR,
+            _ => unreachable!("edge index {}", u),
+        }
+    }
+
     fn decompose(&self) -> (Face, Face) {
         use self::Edge::*;
         match *self {
@@ -77,6 +104,39 @@ impl Edge {
     }
 }
 
+impl ToString for Edge {
+    fn to_string(&self) -> String {
+        use self::Edge::*;
+
+        match *self {
+            UR => "UR",
+            UF => "UF",
+            UL => "UL",
+            UB => "UB",
+            DR => "DR",
+            DF => "DF",
+            DL => "DL",
+            DB => "DB",
+            FR => "FR",
+            FL => "FL",
+            BL => "BL",
+            BR => "BR",
+        }.to_string()
+    }
+}
+
+impl Serialize for Edge {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_name(serializer, &NAMES, usize::from(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Edge {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_name(deserializer, &NAMES).map(Edge::from_u)
+    }
+}
+
 impl From<Edge> for usize {
     fn from(e: Edge) -> Self {
         use self::Edge::*;
@@ -98,7 +158,7 @@ impl From<Edge> for usize {
     }
 }
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Edges {
     pub permutations: [Edge; 12],
     pub orientations: [u8; 12],
@@ -111,16 +171,38 @@ impl Edges {
 
     pub fn multiply(&mut self, m: Move) {
         let m = Move_::move_definition(m);
+
+        self.multiply_arrays(&m.edges_permutation, &m.edges_orientation);
+    }
+
+    /// Apply the edges of `other` on top of `self`.
+    pub fn multiply_by(&mut self, other: &Edges) {
+        self.multiply_arrays(&other.permutations, &other.orientations);
+    }
+
+    fn multiply_arrays(&mut self, permutations: &[Edge; 12], orientations: &[u8; 12]) {
         let mut new_edges = Self::new();
 
         for edge in usize::from(Edge::UR)..usize::from(Edge::BR) + 1 {
-            let index = usize::from(m.edges_permutation[edge]);
+            let index = usize::from(permutations[edge]);
 
             new_edges.permutations[edge] = self.permutations[index];
-            new_edges.orientations[edge] = (m.edges_orientation[edge] + self.orientations[index]) % 2;
+            new_edges.orientations[edge] = (orientations[edge] + self.orientations[index]) % 2;
         }
         *self = new_edges;
     }
+
+    pub fn inverse(&self) -> Self {
+        let mut inverse = Self::new();
+
+        for edge in usize::from(Edge::UR)..usize::from(Edge::BR) + 1 {
+            inverse.permutations[usize::from(self.permutations[edge])] = Edge::from_u(edge);
+        }
+        for edge in usize::from(Edge::UR)..usize::from(Edge::BR) + 1 {
+            inverse.orientations[edge] = self.orientations[usize::from(inverse.permutations[edge])];
+        }
+        inverse
+    }
 }
 
 impl Default for Edges {
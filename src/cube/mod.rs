@@ -1,16 +1,81 @@
-mod face;
+pub mod face;
+pub mod color;
 pub mod corners;
 pub mod edges;
+pub mod cycles;
+pub mod pattern;
+pub mod symmetry;
 
 use cube::face::Face;
+use cube::color::ColorScheme;
 use cube::corners::Corners;
 use cube::corners::Corner;
 use cube::edges::Edges;
 use cube::edges::Edge;
 use move_::Move;
 use move_::UserMove;
+use render;
+use render::Renderer;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde::de;
+
+use std::io;
+use std::io::Write;
+
+/// Number of legal states: 8! * 3^7 * 12! * 2^11 / 2.
+pub const NB_STATES: u128 = 43_252_003_274_489_856_000;
+
+/// Faces in the order of facelet strings.
+const FACELET_ORDER: [Face; 6] = [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B];
+
+/// Positions in `Cube::face` of the corners and edges of `face_cubicles`.
+const CORNER_STICKERS: [usize; 4] = [0, 2, 8, 6];
+const EDGE_STICKERS: [usize; 4] = [1, 5, 7, 3];
+
+/// Edges of the M, S and E slices, in increasing order.
+const SLICES: [[Edge; 4]; 3] = [
+    [Edge::UF, Edge::UB, Edge::DF, Edge::DB],
+    [Edge::UR, Edge::UL, Edge::DR, Edge::DL],
+    [Edge::FR, Edge::FL, Edge::BL, Edge::BR],
+];
+
+/// Why a facelet string does not describe a cube.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum FaceletError {
+    /// Number of stickers, instead of 54.
+    Length(usize),
+    /// A letter of no color of the scheme.
+    Color(char),
+    /// A face whose center has the color of another face.
+    Center(Face),
+    /// Stickers matching no piece, or the same piece twice.
+    Piece,
+    /// A twisted corner.
+    Twist,
+    /// A flipped edge.
+    Flip,
+    /// Two swapped pieces.
+    Parity,
+}
+
+impl ToString for FaceletError {
+    fn to_string(&self) -> String {
+        match *self {
+            FaceletError::Length(n) => format!("expected 54 stickers, got {}", n),
+            FaceletError::Color(c) => format!("unknown color '{}'", c),
+            FaceletError::Center(face) => format!("wrong center on face {}", face.to_string()),
+            FaceletError::Piece => "stickers do not make valid pieces".to_string(),
+            FaceletError::Twist => "a corner is twisted".to_string(),
+            FaceletError::Flip => "an edge is flipped".to_string(),
+            FaceletError::Parity => "two pieces are swapped".to_string(),
+        }
+    }
+}
 
-#[derive(Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 pub struct Cube {
     corners: Corners,
     edges: Edges,
@@ -25,13 +90,19 @@ impl Cube {
             edges: Edges::new(),
             };
 
-        for m in shuffle_sequence.into_iter() {
+        new.apply_sequence(shuffle_sequence);
+        new
+    }
+
+    pub fn apply_sequence<I>(&mut self, sequence: I)
+        where I: IntoIterator<Item=(UserMove, usize)>
+    {
+        for m in sequence.into_iter() {
             let mprime = m.1;
             for _ in 0..mprime {
-                new.apply_move(m.0.to_move());
+                self.apply_move(m.0.to_move());
             }
         }
-        new
     }
 
     pub fn new_default() -> Self {
@@ -42,6 +113,14 @@ impl Cube {
         *self == Self::default()
     }
 
+    pub fn corners(&self) -> &Corners {
+        &self.corners
+    }
+
+    pub fn edges(&self) -> &Edges {
+        &self.edges
+    }
+
     pub fn corners_multiply(&mut self, m: Move) {
         self.corners.multiply(m);
     }
@@ -55,6 +134,20 @@ impl Cube {
         self.edges.multiply(m);
     }
 
+    /// Apply the whole state of `other` on top of `self`, as if the moves
+    /// leading to `other` were played after the ones leading to `self`.
+    pub fn multiply_by(&mut self, other: &Cube) {
+        self.corners.multiply_by(&other.corners);
+        self.edges.multiply_by(&other.edges);
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self {
+            corners: self.corners.inverse(),
+            edges: self.edges.inverse(),
+        }
+    }
+
     pub fn twist(&self) -> u32 {
         let mut ret: u32 = 0;
 
@@ -110,6 +203,19 @@ impl Cube {
         ret % 2
     }
 
+    pub fn edge_parity(&self) -> u32 {
+        let mut ret: u32 = 0;
+
+        for x in (usize::from(Edge::UR) + 1..=usize::from(Edge::BR)).rev() {
+            for y in (usize::from(Edge::UR)..x).rev() {
+                if usize::from(self.edges.permutations[y]) > usize::from(self.edges.permutations[x]) {
+                    ret += 1;
+                }
+            }
+        }
+        ret % 2
+    }
+
     pub fn fr_to_br(&self) -> u32 {
         let mut a: u32 = 0;
         let mut x: u32 = 0;
@@ -395,6 +501,191 @@ impl Cube {
         }
     }
 
+    /// Rank of the whole corners permutation, in [0, 8!).
+    /// It tells both which tetrad every corner lies in and the corners parity.
+    pub fn corner_permutation(&self) -> u32 {
+        let mut ret: u32 = 0;
+
+        for x in usize::from(Corner::URF)..=usize::from(Corner::DRB) {
+            let mut smaller: u32 = 0;
+            for y in x + 1..=usize::from(Corner::DRB) {
+                if usize::from(self.corners.permutations[y]) < usize::from(self.corners.permutations[x]) {
+                    smaller += 1;
+                }
+            }
+            ret = (8 - x as u32) * ret + smaller;
+        }
+        ret
+    }
+
+    pub fn set_corner_permutation(&mut self, index: u32) {
+        let mut index = index;
+        let mut digits: [usize; 8] = [0; 8];
+        let mut left: Vec<usize> = (0..8).collect();
+
+        for x in (usize::from(Corner::URF)..=usize::from(Corner::DRB)).rev() {
+            digits[x] = (index % (8 - x as u32)) as usize;
+            index /= 8 - x as u32;
+        }
+        for x in usize::from(Corner::URF)..=usize::from(Corner::DRB) {
+            self.corners.permutations[x] = Corner::from_u(left.remove(digits[x]));
+        }
+    }
+
+    /// Rank of the permutation of the 12 edges, in [0, 12!).
+    pub fn edge_permutation(&self) -> u32 {
+        let mut ret: u32 = 0;
+
+        for x in usize::from(Edge::UR)..=usize::from(Edge::BR) {
+            let mut smaller: u32 = 0;
+            for y in x + 1..=usize::from(Edge::BR) {
+                if usize::from(self.edges.permutations[y]) < usize::from(self.edges.permutations[x]) {
+                    smaller += 1;
+                }
+            }
+            ret = (12 - x as u32) * ret + smaller;
+        }
+        ret
+    }
+
+    pub fn set_edge_permutation(&mut self, index: u32) {
+        let mut index = index;
+        let mut digits: [usize; 12] = [0; 12];
+        let mut left: Vec<usize> = (0..12).collect();
+
+        for x in (usize::from(Edge::UR)..=usize::from(Edge::BR)).rev() {
+            digits[x] = (index % (12 - x as u32)) as usize;
+            index /= 12 - x as u32;
+        }
+        for x in usize::from(Edge::UR)..=usize::from(Edge::BR) {
+            self.edges.permutations[x] = Edge::from_u(left.remove(digits[x]));
+        }
+    }
+
+    /// Unique index of the state, in [0, `NB_STATES`), which takes 66 bits.
+    ///
+    /// Made of the corner permutation, twist, edge permutation and flip. The
+    /// last bit of the edge permutation rank is left out, as it follows from
+    /// the parity of the corners.
+    pub fn to_index(&self) -> u128 {
+        let corners = u128::from(self.corner_permutation()) * 2187 + u128::from(self.twist());
+        let edges = u128::from(self.edge_permutation() / 2) * 2048 + u128::from(self.flip());
+
+        corners * (479_001_600 / 2 * 2048) + edges
+    }
+
+    /// The state of `index`, or `None` if it is not below `NB_STATES`.
+    pub fn from_index(index: u128) -> Option<Self> {
+        if index >= NB_STATES {
+            return None;
+        }
+        let (corners, edges) = (index / (479_001_600 / 2 * 2048), index % (479_001_600 / 2 * 2048));
+        let mut cube = Self::new_default();
+
+        cube.set_corner_permutation((corners / 2187) as u32);
+        cube.set_twist((corners % 2187) as i16);
+        cube.set_edge_permutation((edges / 2048) as u32 * 2);
+        if cube.edge_parity() != cube.corner_parity() {
+            cube.set_edge_permutation((edges / 2048) as u32 * 2 + 1);
+        }
+        cube.set_flip((edges % 2048) as i16);
+        Some(cube)
+    }
+
+    /// A random state, all of them about equally likely, always the same for
+    /// the same `seed`.
+    pub fn random(seed: u64) -> Self {
+        let mut state = seed ^ 0x9e37_79b9_7f4a_7c15;
+        let mut index: u128 = 0;
+
+        for _ in 0..2 {
+            // xorshift64*
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            index = index << 64 | state.wrapping_mul(0x2545_f491_4f6c_dd1d) as u128;
+        }
+        Self::from_index(index % NB_STATES).unwrap()
+    }
+
+    /// Positions of the M slice edges UF, UB, DF and DB, in [0, 495).
+    pub fn m_slice(&self) -> u32 {
+        let mut a: u32 = 0;
+        let mut x: i16 = 0;
+
+        for i in usize::from(Edge::UR)..=usize::from(Edge::BR) {
+            match self.edges.permutations[i] {
+                Edge::UF | Edge::UB | Edge::DF | Edge::DB => {
+                    a += cnk(i as i16, x + 1) as u32;
+                    x += 1;
+                },
+                _ => {},
+            }
+        }
+        a
+    }
+
+    pub fn set_m_slice(&mut self, index: i16) {
+        let mut a = index;
+        let edges: [Edge; 4] = [Edge::UF, Edge::UB, Edge::DF, Edge::DB];
+        let others: [Edge; 8] = [Edge::UR, Edge::UL, Edge::DR, Edge::DL, Edge::FR, Edge::FL, Edge::BL, Edge::BR];
+
+        let mut z: i16 = 3;
+        for x in (usize::from(Edge::UR)..=usize::from(Edge::BR)).rev() {
+            if z >= 0 && a - cnk(x as i16, z + 1) >= 0 {
+                self.edges.permutations[x] = edges[z as usize];
+                a -= cnk(x as i16, z + 1);
+                z -= 1;
+            } else {
+                self.edges.permutations[x] = Edge::UR;
+            }
+        }
+        z = 0;
+        for x in usize::from(Edge::UR)..=usize::from(Edge::BR) {
+            if self.edges.permutations[x] == Edge::UR {
+                self.edges.permutations[x] = others[z as usize];
+                z += 1;
+            }
+        }
+    }
+
+    /// Permutation of the edges inside each of the M, S and E slices, in
+    /// [0, 24^3). Only meaningful once every edge lies in its own slice.
+    pub fn slice_permutations(&self) -> u32 {
+        let mut ret: u32 = 0;
+
+        for slice in SLICES.iter() {
+            for x in 0..4 {
+                let mut smaller: u32 = 0;
+                for y in x + 1..4 {
+                    if usize::from(self.edges.permutations[usize::from(slice[y])])
+                        < usize::from(self.edges.permutations[usize::from(slice[x])]) {
+                        smaller += 1;
+                    }
+                }
+                ret = (4 - x as u32) * ret + smaller;
+            }
+        }
+        ret
+    }
+
+    pub fn set_slice_permutations(&mut self, index: u32) {
+        let mut index = index;
+
+        for slice in SLICES.iter().rev() {
+            let mut digits: [usize; 4] = [0; 4];
+            let mut left: Vec<Edge> = slice.to_vec();
+
+            for x in (0..4).rev() {
+                digits[x] = (index % (4 - x as u32)) as usize;
+                index /= 4 - x as u32;
+            }
+            for x in 0..4 {
+                self.edges.permutations[usize::from(slice[x])] = left.remove(digits[x]);
+            }
+        }
+    }
+
     pub fn ur_to_uf_standalone(index1: i16, index2: i16) -> i16 {
         let mut a: Cube = Cube::new_default();
         let mut b: Cube = Cube::new_default();
@@ -425,7 +716,9 @@ impl Cube {
         }
     }
 
-    fn face(&self, face: Face) -> [Face; 9] {
+    /// Corners and edges of `face`, clockwise from its top left corner and
+    /// its top edge.
+    fn face_cubicles(face: Face) -> ([Corner; 4], [Edge; 4]) {
         use self::corners::Corner::*;
         let corners = match face {
             Face::F => [UFL, URF, DFR, DLF],
@@ -436,14 +729,6 @@ impl Cube {
             Face::R => [URF, UBR, DRB, DFR],
         };
 
-        let mut corner_faces: [self::Face; 4] = [self::Face::F; 4];
-
-        for (i, c) in (&corners).iter().enumerate() {
-            let corner_cubie: corners::Corner = self.corners.permutations[usize::from(*c)];
-
-            corner_faces[i] = corner_cubie.face(*c, self.corners.orientations[usize::from(*c)], face);
-        }
-
         use self::edges::Edge::*;
         let edges = match face {
             Face::F => [UF, FR, DF, FL],
@@ -453,58 +738,176 @@ impl Cube {
             Face::L => [UL, FL, DL, BL],
             Face::R => [UR, BR, DR, FR],
         };
+        (corners, edges)
+    }
 
-        let mut edge_faces: [self::Face; 4] = [self::Face::F; 4];
+    /// Stickers of `face`, row by row as seen when facing it.
+    pub fn face(&self, face: Face) -> [Face; 9] {
+        let (corners, edges) = Self::face_cubicles(face);
+        let mut stickers = [face; 9];
 
+        for (i, c) in (&corners).iter().enumerate() {
+            let corner_cubie: corners::Corner = self.corners.permutations[usize::from(*c)];
+
+            stickers[CORNER_STICKERS[i]] = corner_cubie.face(*c, self.corners.orientations[usize::from(*c)], face);
+        }
         for (i, e) in (&edges).iter().enumerate() {
             let edge_cubie: edges::Edge = self.edges.permutations[usize::from(*e)];
 
-            edge_faces[i] = edge_cubie.face(*e, self.edges.orientations[usize::from(*e)], face);
+            stickers[EDGE_STICKERS[i]] = edge_cubie.face(*e, self.edges.orientations[usize::from(*e)], face);
         }
-
-        [corner_faces[0], edge_faces[0], corner_faces[1],
-        edge_faces[3], face, edge_faces[1],
-        corner_faces[3], edge_faces[2], corner_faces[2]]
+        stickers
     }
 
-    pub fn print(&self) {
-        let faces = [
-            self.face(self::Face::U),
-            self.face(self::Face::L),
-            self.face(self::Face::F),
-            self.face(self::Face::R),
-            self.face(self::Face::B),
-            self.face(self::Face::D),
-        ];
-
-        print!("\n          ");
-        for i in 0..9 {
-            print!("{} {} \x1b[0m", faces[0][i].color(), faces[0][i].to_string());
-            if i > 0 && (i+1) % 3 == 0 {
-                print!("\n          ");
-            }
-        }
-        print!("\r\n");
-        for y in 0..3 {
-            for &face in &faces {
-                if face[4] != self::Face::U && face[4] != self::Face::D {
-                    for x in 0..3 {
-                        print!("{} {} \x1b[0m", face[x+y*3].color(), face[x+y*3].to_string());
-                    }
-                    print!(" ");
-                }
+    /// Read the 54 sticker colors of the U, R, F, D, L and B faces, each
+    /// face row by row as in `Cube::face`. Whitespace is ignored.
+    pub fn from_facelets(facelets: &str, scheme: &ColorScheme) -> Result<Self, FaceletError> {
+        let letters: Vec<char> = facelets.chars().filter(|c| !c.is_whitespace()).collect();
+        if letters.len() != 54 {
+            return Err(FaceletError::Length(letters.len()));
+        }
+
+        // Sticker on each face of each cubicle.
+        let mut corner_stickers = [[None; 6]; 8];
+        let mut edge_stickers = [[None; 6]; 12];
+        for (n, &face) in FACELET_ORDER.iter().enumerate() {
+            let mut stickers = [face; 9];
+            for i in 0..9 {
+                stickers[i] = scheme.face(letters[9 * n + i]).ok_or(FaceletError::Color(letters[9 * n + i]))?;
+            }
+            if stickers[4] != face {
+                return Err(FaceletError::Center(face));
+            }
+
+            let (corners, edges) = Self::face_cubicles(face);
+            for i in 0..4 {
+                corner_stickers[usize::from(corners[i])][face as usize] = Some(stickers[CORNER_STICKERS[i]]);
+                edge_stickers[usize::from(edges[i])][face as usize] = Some(stickers[EDGE_STICKERS[i]]);
             }
-            println!();
         }
-        print!("\n          ");
-        for i in 0..9 {
-            print!("{} {} \x1b[0m", faces[5][i].color(), faces[5][i].to_string());
 
-            if i > 0 && (i+1) % 3 == 0 {
-                print!("\n          ");
+        let mut cube = Self::new_default();
+        let mut corners_used = [false; 8];
+        let mut edges_used = [false; 12];
+        for c in 0..8 {
+            let cubicle = Corner::from_u(c);
+            let shows = |cubie: Corner, orientation: u8| {
+                FACELET_ORDER.iter().all(|&face| match corner_stickers[c][face as usize] {
+                    Some(sticker) => cubie.face(cubicle, orientation, face) == sticker,
+                    None => true,
+                })
+            };
+            let (cubie, orientation) = (0..24)
+                .map(|x| (Corner::from_u(x / 3), (x % 3) as u8))
+                .find(|&(cubie, orientation)| shows(cubie, orientation))
+                .ok_or(FaceletError::Piece)?;
+            if corners_used[usize::from(cubie)] {
+                return Err(FaceletError::Piece);
+            }
+            corners_used[usize::from(cubie)] = true;
+            cube.corners.permutations[c] = cubie;
+            cube.corners.orientations[c] = orientation;
+        }
+        for e in 0..12 {
+            let cubicle = Edge::from_u(e);
+            let shows = |cubie: Edge, orientation: u8| {
+                FACELET_ORDER.iter().all(|&face| match edge_stickers[e][face as usize] {
+                    Some(sticker) => cubie.face(cubicle, orientation, face) == sticker,
+                    None => true,
+                })
+            };
+            let (cubie, orientation) = (0..24)
+                .map(|x| (Edge::from_u(x / 2), (x % 2) as u8))
+                .find(|&(cubie, orientation)| shows(cubie, orientation))
+                .ok_or(FaceletError::Piece)?;
+            if edges_used[usize::from(cubie)] {
+                return Err(FaceletError::Piece);
             }
+            edges_used[usize::from(cubie)] = true;
+            cube.edges.permutations[e] = cubie;
+            cube.edges.orientations[e] = orientation;
+        }
+
+        cube.check()?;
+        Ok(cube)
+    }
+
+    /// Check that the cube can be solved: each piece appears once, and the
+    /// orientations and permutations are those of a legal state.
+    pub fn check(&self) -> Result<(), FaceletError> {
+        let mut corners_used = [false; 8];
+        let mut edges_used = [false; 12];
+        for &corner in self.corners.permutations.iter() {
+            corners_used[usize::from(corner)] = true;
+        }
+        for &edge in self.edges.permutations.iter() {
+            edges_used[usize::from(edge)] = true;
+        }
+        if corners_used.contains(&false) || edges_used.contains(&false) {
+            return Err(FaceletError::Piece);
+        }
+
+        if self.corners.orientations.iter().any(|&x| x > 2)
+            || self.corners.orientations.iter().sum::<u8>() % 3 != 0 {
+            return Err(FaceletError::Twist);
+        }
+        if self.edges.orientations.iter().any(|&x| x > 1)
+            || self.edges.orientations.iter().sum::<u8>() % 2 != 0 {
+            return Err(FaceletError::Flip);
+        }
+        if self.corner_parity() != self.edge_parity() {
+            return Err(FaceletError::Parity);
+        }
+        Ok(())
+    }
+
+    /// The 54 sticker colors, in the order read by `Cube::from_facelets`.
+    pub fn to_facelets(&self, scheme: &ColorScheme) -> String {
+        FACELET_ORDER.iter()
+            .flat_map(|&face| self.face(face).to_vec())
+            .map(|sticker| scheme.color(sticker).letter)
+            .collect()
+    }
+
+    /// Print the net of the cube on stdout, with the renderer of
+    /// `render::from_env`.
+    pub fn print(&self) {
+        let stdout = io::stdout();
+        let _ = render::write(self, &*render::from_env(), &mut stdout.lock());
+    }
+
+    pub fn render(&self, renderer: &Renderer) -> String {
+        render::render(self, renderer)
+    }
+
+    pub fn write_to<W: Write>(&self, renderer: &Renderer, out: &mut W) -> io::Result<()> {
+        render::write(self, renderer, out)
+    }
+}
+
+/// The facelet string in the western color scheme in human readable formats,
+/// the corners and edges otherwise.
+impl Serialize for Cube {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_facelets(&ColorScheme::western()))
+        } else {
+            (&self.corners, &self.edges).serialize(serializer)
         }
-        println!();
+    }
+}
+
+impl<'de> Deserialize<'de> for Cube {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cube = if deserializer.is_human_readable() {
+            let facelets = String::deserialize(deserializer)?;
+            Self::from_facelets(&facelets, &ColorScheme::western())
+        } else {
+            let (corners, edges) = <(Corners, Edges)>::deserialize(deserializer)?;
+            let cube = Self { corners: corners, edges: edges };
+            cube.check().map(|_| cube)
+        };
+        cube.map_err(|e| de::Error::custom(e.to_string()))
     }
 }
 
@@ -538,3 +941,21 @@ pub fn cnk(n: i16, mut k: i16) -> i16 {
     }
     s
 }
+
+#[cfg(test)]
+mod tests {
+    use cube::Cube;
+    use cube::NB_STATES;
+
+    #[test]
+    fn index_round_trip() {
+        for &index in [0, 1, NB_STATES / 2, NB_STATES - 1].iter() {
+            assert_eq!(Cube::from_index(index).unwrap().to_index(), index);
+        }
+        for seed in 0..1000 {
+            let cube = Cube::random(seed);
+            assert!(Cube::from_index(cube.to_index()).unwrap() == cube);
+        }
+        assert!(Cube::from_index(NB_STATES).is_none());
+    }
+}
@@ -0,0 +1,68 @@
+use cube::Cube;
+use move_::UserMove;
+
+/// A named pretty pattern and a shortest sequence leading to it, in the half
+/// turn metric.
+pub struct Pattern {
+    pub name: &'static str,
+    pub sequence: &'static str,
+}
+
+impl Pattern {
+    pub fn sequence(&self) -> Vec<(UserMove, usize)> {
+        UserMove::sequence_from_str(self.sequence).unwrap()
+    }
+
+    pub fn cube(&self) -> Cube {
+        Cube::from_shuffle_sequence(self.sequence())
+    }
+
+    pub fn find(name: &str) -> Option<&'static Pattern> {
+        PATTERNS.iter().find(|pattern| pattern.name == name)
+    }
+}
+
+pub const PATTERNS: [Pattern; 12] = [
+    Pattern { name: "superflip", sequence: "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2" },
+    Pattern { name: "checkerboard", sequence: "U2 D2 F2 B2 L2 R2" },
+    Pattern { name: "cube-in-cube", sequence: "F L F U' R U F2 L2 U' L' B D' B' L2 U" },
+    Pattern { name: "cube-in-cube-in-cube", sequence: "U' L' U' F' R2 B' R F U B2 U B' L U' F U R F'" },
+    Pattern { name: "six-spot", sequence: "U D' R L' F B' U D'" },
+    Pattern { name: "four-spot", sequence: "F2 B2 U D' R2 L2 U D'" },
+    Pattern { name: "cross", sequence: "U F B' L2 U2 L2 F' B U2 L2 U" },
+    Pattern { name: "plummer-cross", sequence: "R2 L' D F2 R' D' R' L U' D R D B2 R' U D2" },
+    Pattern { name: "anaconda", sequence: "L U B' U' R L' B R' F B' D R D' F'" },
+    Pattern { name: "python", sequence: "F2 R' B' U R' L F' L F' B D' R B L2" },
+    Pattern { name: "spiral", sequence: "L' B' D U R U' R' D2 R2 D L D' L' R' F U" },
+    Pattern { name: "vertical-stripes", sequence: "F U F R L2 B D' R D2 L D' B R2 L F U F" },
+];
+
+impl Cube {
+    /// The state of the pattern called `name` in `PATTERNS`.
+    pub fn from_pattern(name: &str) -> Option<Self> {
+        Pattern::find(name).map(|pattern| pattern.cube())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use coordinate::Coordinate;
+    use cube::Cube;
+    use cube::pattern::PATTERNS;
+    use solver::Solver;
+
+    #[test]
+    fn solve_every_pattern() {
+        let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+        coordinate.init_pruning();
+        let solver = Solver::new(&coordinate);
+
+        for pattern in PATTERNS.iter() {
+            let mut cube = pattern.cube();
+            let solution = solver.solve(&cube).unwrap();
+
+            cube.multiply_by(&Cube::from_shuffle_sequence(solution));
+            assert!(cube.is_solved(), "{}", pattern.name);
+        }
+    }
+}
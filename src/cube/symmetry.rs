@@ -0,0 +1,156 @@
+use cube::Cube;
+use cube::corners::Corner;
+use cube::corners::Corners;
+use cube::edges::Edge;
+use cube::edges::Edges;
+
+/// Number of symmetries of the cube: 24 rotations, each with its mirror.
+pub const NB_SYMMETRIES: usize = 48;
+
+fn cube(corners: [Corner; 8], twists: [u8; 8], edges: [Edge; 12], flips: [u8; 12]) -> Cube {
+    Cube {
+        corners: Corners { permutations: corners, orientations: twists },
+        edges: Edges { permutations: edges, orientations: flips },
+    }
+}
+
+/// The 48 symmetries as cubes, with their inverses.
+pub struct Symmetries {
+    /// Symmetry `16 * urf3 + 8 * f2 + 2 * u4 + lr2` is made of `urf3` 120°
+    /// rotations around the URF-DBL diagonal, `f2` half turns around the F-B
+    /// axis, `u4` quarter turns around the U-D axis, then `lr2` reflections
+    /// through the plane between L and R.
+    ///
+    /// Reflections give corners an orientation of 3 to 5, as handled by
+    /// `Cube::multiply_by`.
+    pub cubes: Vec<Cube>,
+    /// Index of the inverse of each symmetry.
+    pub inverses: Vec<usize>,
+}
+
+impl Symmetries {
+    pub fn new() -> Self {
+        use cube::corners::Corner::*;
+        use cube::edges::Edge::*;
+
+        let urf3 = cube([URF, DFR, DLF, UFL, UBR, DRB, DBL, ULB], [1, 2, 1, 2, 2, 1, 2, 1],
+                        [UF, FR, DF, FL, UB, BR, DB, BL, UR, DR, DL, UL], [1, 0, 1, 0, 1, 0, 1, 0, 1, 1, 1, 1]);
+        let f2 = cube([DLF, DFR, DRB, DBL, UFL, URF, UBR, ULB], [0; 8],
+                      [DL, DF, DR, DB, UL, UF, UR, UB, FL, FR, BR, BL], [0; 12]);
+        let u4 = cube([UBR, URF, UFL, ULB, DRB, DFR, DLF, DBL], [0; 8],
+                      [UB, UR, UF, UL, DB, DR, DF, DL, BR, FR, FL, BL], [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1]);
+        let lr2 = cube([UFL, URF, UBR, ULB, DLF, DFR, DRB, DBL], [3; 8],
+                       [UL, UF, UR, UB, DL, DF, DR, DB, FL, FR, BR, BL], [0; 12]);
+
+        let mut cubes = Vec::with_capacity(NB_SYMMETRIES);
+        let mut current = Cube::new_default();
+        for _ in 0..3 {
+            for _ in 0..2 {
+                for _ in 0..4 {
+                    for _ in 0..2 {
+                        cubes.push(current.clone());
+                        current.multiply_by(&lr2);
+                    }
+                    current.multiply_by(&u4);
+                }
+                current.multiply_by(&f2);
+            }
+            current.multiply_by(&urf3);
+        }
+
+        let inverses = cubes.iter()
+            .map(|s| {
+                cubes.iter()
+                    .position(|t| {
+                        let mut product = s.clone();
+                        product.multiply_by(t);
+                        product.is_solved()
+                    })
+                    .unwrap()
+            })
+            .collect();
+        Self { cubes: cubes, inverses: inverses }
+    }
+
+    /// `S^-1 * cube * S` for the symmetry `S` of index `symmetry`: the state
+    /// the moves leading to `cube` lead to once rotated or mirrored.
+    pub fn conjugate(&self, cube: &Cube, symmetry: usize) -> Cube {
+        let mut conjugate = self.cubes[self.inverses[symmetry]].clone();
+
+        conjugate.multiply_by(cube);
+        conjugate.multiply_by(&self.cubes[symmetry]);
+        conjugate
+    }
+
+    /// The 48 states equivalent to `cube` under symmetry, in the order of
+    /// `cubes`, some of them equal if `cube` is symmetric.
+    pub fn symmetric_states(&self, cube: &Cube) -> Vec<Cube> {
+        (0..NB_SYMMETRIES).map(|symmetry| self.conjugate(cube, symmetry)).collect()
+    }
+
+    /// The representative of the states equivalent to `cube` under symmetry,
+    /// the one with the smallest `to_index`.
+    pub fn canonical(&self, cube: &Cube) -> Cube {
+        self.symmetric_states(cube).into_iter().min_by_key(|cube| cube.to_index()).unwrap()
+    }
+
+    /// Same as `canonical`, with the inverse of a state also counted as
+    /// equivalent to it: the solutions of one are the inverses of the
+    /// solutions of the other.
+    pub fn canonical_with_inverse(&self, cube: &Cube) -> Cube {
+        let (state, inverse) = (self.canonical(cube), self.canonical(&cube.inverse()));
+
+        if inverse.to_index() < state.to_index() { inverse } else { state }
+    }
+}
+
+impl Default for Symmetries {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+thread_local! {
+    /// Built once per thread for the methods of `Cube` below.
+    static SYMMETRIES: Symmetries = Symmetries::new();
+}
+
+impl Cube {
+    /// `Symmetries::symmetric_states` of `self`.
+    pub fn symmetric_states(&self) -> Vec<Self> {
+        SYMMETRIES.with(|symmetries| symmetries.symmetric_states(self))
+    }
+
+    /// `Symmetries::canonical` of `self`.
+    pub fn canonical(&self) -> Self {
+        SYMMETRIES.with(|symmetries| symmetries.canonical(self))
+    }
+
+    /// `Symmetries::canonical_with_inverse` of `self`.
+    pub fn canonical_with_inverse(&self) -> Self {
+        SYMMETRIES.with(|symmetries| symmetries.canonical_with_inverse(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cube::Cube;
+    use cube::symmetry::NB_SYMMETRIES;
+    use cube::symmetry::Symmetries;
+
+    #[test]
+    fn canonical_is_invariant_under_conjugation() {
+        let symmetries = Symmetries::new();
+
+        for seed in 0..20 {
+            let cube = Cube::random(seed);
+            let (canonical, with_inverse) = (symmetries.canonical(&cube), symmetries.canonical_with_inverse(&cube));
+            for symmetry in 0..NB_SYMMETRIES {
+                let conjugate = symmetries.conjugate(&cube, symmetry);
+                assert!(symmetries.canonical(&conjugate) == canonical);
+                assert!(symmetries.canonical_with_inverse(&conjugate) == with_inverse);
+                assert!(symmetries.canonical_with_inverse(&conjugate.inverse()) == with_inverse);
+            }
+        }
+    }
+}
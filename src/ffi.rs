@@ -0,0 +1,202 @@
+//! C interface to the two-phase solver, declared in `include/rubik.h`.
+//!
+//! Strings returned by the library are owned by the caller, and freed with
+//! `rubik_free_solution`.
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+use std::slice;
+use std::time::Duration;
+
+use coordinate::Coordinate;
+use coordinate::store::Blob;
+use coordinate::store::Directory;
+use cube::Cube;
+use cube::color::ColorScheme;
+use move_::UserMove;
+use solver::Solver;
+
+/// Loaded tables, opaque to C.
+pub struct RubikTables {
+    coordinate: Coordinate,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum RubikStatus {
+    Ok = 0,
+    /// A null pointer, or a string which is not UTF-8.
+    InvalidArgument = 1,
+    /// The facelets do not make a solvable cube, or the scramble is not a
+    /// valid move sequence.
+    InvalidCube = 2,
+    /// None within the maximum depth, or before the timeout.
+    NoSolution = 3,
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Read the tables from the `cache_dir` folder, or generate and cache them
+/// there. `pruning_tables` if `cache_dir` is null.
+///
+/// Free the tables with `rubik_tables_free`.
+///
+/// # Safety
+///
+/// `cache_dir` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_tables_load(cache_dir: *const c_char) -> *mut RubikTables {
+    let cache_dir = match (cache_dir.is_null(), to_str(cache_dir)) {
+        (true, _) => "pruning_tables",
+        (false, Some(cache_dir)) => cache_dir,
+        (false, None) => return ptr::null_mut(),
+    };
+    let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+
+    coordinate.init_pruning_from(&mut Directory::new(cache_dir));
+    Box::into_raw(Box::new(RubikTables { coordinate: coordinate }))
+}
+
+/// Load the tables from a blob written by `rubik tables`.
+///
+/// Returns null if the blob is invalid.
+///
+/// # Safety
+///
+/// `data` must be null or point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_tables_from_blob(data: *const u8, len: usize) -> *mut RubikTables {
+    if data.is_null() {
+        return ptr::null_mut();
+    }
+    let mut blob = match Blob::from_bytes(slice::from_raw_parts(data, len)) {
+        Some(blob) => blob,
+        None => return ptr::null_mut(),
+    };
+    let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+
+    coordinate.init_pruning_from(&mut blob);
+    Box::into_raw(Box::new(RubikTables { coordinate: coordinate }))
+}
+
+/// Free tables returned by `rubik_tables_load` or `rubik_tables_from_blob`.
+///
+/// # Safety
+///
+/// `tables` must be null or a `RubikTables*` returned by one of them, not
+/// freed yet. It must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_tables_free(tables: *mut RubikTables) {
+    if !tables.is_null() {
+        drop(Box::from_raw(tables));
+    }
+}
+
+/// Check that `facelets`, 54 letters of the western color scheme for the U,
+/// R, F, D, L and B faces, each face row by row, make a solvable cube.
+///
+/// # Safety
+///
+/// `facelets` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_verify_facelets(facelets: *const c_char) -> RubikStatus {
+    match to_str(facelets) {
+        Some(facelets) => match Cube::from_facelets(facelets, &ColorScheme::western()) {
+            Ok(_) => RubikStatus::Ok,
+            Err(_) => RubikStatus::InvalidCube,
+        },
+        None => RubikStatus::InvalidArgument,
+    }
+}
+
+unsafe fn solve(tables: *const RubikTables, cube: Result<Cube, RubikStatus>, max_depth: u8, timeout_ms: u32,
+                solution: *mut *mut c_char) -> RubikStatus {
+    if tables.is_null() || solution.is_null() {
+        return RubikStatus::InvalidArgument;
+    }
+    *solution = ptr::null_mut();
+    let cube = match cube {
+        Ok(cube) => cube,
+        Err(status) => return status,
+    };
+    let mut solver = Solver::new(&(*tables).coordinate).max_depth(max_depth);
+    if timeout_ms > 0 {
+        solver = solver.timeout(Duration::from_millis(u64::from(timeout_ms)));
+    }
+
+    match solver.solve(&cube) {
+        Some(moves) => {
+            *solution = CString::new(UserMove::sequence_to_string(&moves)).unwrap().into_raw();
+            RubikStatus::Ok
+        },
+        None => RubikStatus::NoSolution,
+    }
+}
+
+/// Solve the cube with these facelets, as in `rubik_verify_facelets`, in at
+/// most `max_depth` moves, giving up after `timeout_ms` milliseconds unless
+/// it is 0.
+///
+/// On success, `*solution` is set to the moves separated by spaces.
+/// Free it with `rubik_free_solution`.
+///
+/// # Safety
+///
+/// `tables` must be null or a valid `RubikTables*` from `rubik_tables_load`
+/// or `rubik_tables_from_blob`, `facelets` null or a NUL-terminated string,
+/// and `solution` null or point to a writable `char*`.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_solve_facelets(tables: *const RubikTables, facelets: *const c_char, max_depth: u8,
+                                              timeout_ms: u32, solution: *mut *mut c_char) -> RubikStatus {
+    let cube = match to_str(facelets) {
+        Some(facelets) => Cube::from_facelets(facelets, &ColorScheme::western())
+            .map_err(|_| RubikStatus::InvalidCube),
+        None => Err(RubikStatus::InvalidArgument),
+    };
+    solve(tables, cube, max_depth, timeout_ms, solution)
+}
+
+/// Solve the cube `scramble` leads to, like `rubik_solve_facelets`.
+///
+/// # Safety
+///
+/// As for `rubik_solve_facelets`, `scramble` being null or a NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_solve_scramble(tables: *const RubikTables, scramble: *const c_char, max_depth: u8,
+                                              timeout_ms: u32, solution: *mut *mut c_char) -> RubikStatus {
+    let cube = match to_str(scramble) {
+        Some(scramble) => UserMove::sequence_from_str(scramble)
+            .map(Cube::from_shuffle_sequence)
+            .map_err(|_| RubikStatus::InvalidCube),
+        None => Err(RubikStatus::InvalidArgument),
+    };
+    solve(tables, cube, max_depth, timeout_ms, solution)
+}
+
+/// `length` random moves from `seed`, to free with `rubik_free_solution`.
+#[no_mangle]
+pub extern "C" fn rubik_scramble(length: u32, seed: u64) -> *mut c_char {
+    CString::new(UserMove::sequence_to_string(&UserMove::random_sequence(length as usize, seed))).unwrap().into_raw()
+}
+
+/// Free a string returned by the library.
+///
+/// # Safety
+///
+/// `solution` must be null or a string returned by `rubik_scramble`,
+/// `rubik_solve_facelets` or `rubik_solve_scramble`, not freed yet, and
+/// never freed any other way.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_free_solution(solution: *mut c_char) {
+    if !solution.is_null() {
+        drop(CString::from_raw(solution));
+    }
+}
@@ -0,0 +1,34 @@
+#![feature(inclusive_range_syntax)]
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(i128_type)]
+
+extern crate test;
+extern crate bincode;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[cfg(test)]
+extern crate serde_json;
+#[cfg(feature = "python")]
+extern crate pyo3;
+// The code generated by pyo3 refers to `::core`, which in the 2015 edition
+// must be a crate at the root.
+#[cfg(feature = "python")]
+extern crate core;
+
+pub mod algorithms;
+pub mod move_;
+pub mod cube;
+pub mod coordinate;
+pub mod solver;
+pub mod render;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
+pub mod serialization;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ffi;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+#[cfg(feature = "python")]
+pub mod python;
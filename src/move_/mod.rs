@@ -1,5 +1,32 @@
+mod parser;
+
+use cube::Cube;
 use cube::corners::Corner;
 use cube::edges::Edge;
+use cube::face::Face;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serialization::deserialize_name;
+use serialization::serialize_name;
+
+const MOVE_NAMES: [&str; 6] = ["U", "R", "F", "D", "L", "B"];
+
+/// Notation of each `UserMove`, in the order of the variants.
+const USER_MOVE_NAMES: [&str; 18] = [
+    "F", "F'", "F2", "R", "R'", "R2", "U", "U'", "U2", "B", "B'", "B2", "L", "L'", "L2", "D", "D'", "D2",
+];
+
+/// Each `UserMove`, in the order of the variants.
+const USER_MOVES: [UserMove; 18] = [
+    UserMove::Front, UserMove::FrontPrime, UserMove::Front2,
+    UserMove::Right, UserMove::RightPrime, UserMove::Right2,
+    UserMove::Up, UserMove::UpPrime, UserMove::Up2,
+    UserMove::Back, UserMove::BackPrime, UserMove::Back2,
+    UserMove::Left, UserMove::LeftPrime, UserMove::Left2,
+    UserMove::Down, UserMove::DownPrime, UserMove::Down2,
+];
 
 #[derive(Clone, Copy)]
 pub enum Move {
@@ -25,6 +52,18 @@ impl Move {
     }
 }
 
+impl Serialize for Move {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_name(serializer, &MOVE_NAMES, *self as usize)
+    }
+}
+
+impl<'de> Deserialize<'de> for Move {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_name(deserializer, &MOVE_NAMES).map(Move::from_u)
+    }
+}
+
 pub struct Move_ {
     pub corners_permutation: [Corner; 8],
     pub corners_orientation: [u8; 8],
@@ -78,6 +117,17 @@ impl Move_ {
     }
 }
 
+/// How `UserMove::sequence_from_notation` reads moves.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Notation {
+    /// `R`, `R'` and `R2` only, separated by spaces.
+    Strict,
+    /// Also `R2'`, `R'2`, `R3`, `Ri` and `R’`, moves written together as in
+    /// `RUR'U'`, wide moves `Rw` and `r`, slices `M`, `E` and `S`, and
+    /// rotations `x`, `y` and `z`, as found in scrambles from timer apps.
+    Lenient,
+}
+
 #[derive(Clone, Copy)]
 pub enum UserMove {
     Front,
@@ -101,36 +151,139 @@ pub enum UserMove {
 }
 
 impl UserMove {
+    /// Parse moves such as `R U R' U'`, with commutators `[A, B]`,
+    /// conjugates `[A: B]`, repetitions `(A)2` and comments expanded or
+    /// skipped, in the lenient notation.
     pub fn sequence_from_str(s: &str) -> Result<Vec<(Self, usize)>, ()> {
+        Self::sequence_from_notation(s, Notation::Lenient)
+    }
+
+    /// Same as `sequence_from_str`, in `notation`. Wide moves, slices and
+    /// rotations become face turns leading to the same state, the centers
+    /// staying in place.
+    pub fn sequence_from_notation(s: &str, notation: Notation) -> Result<Vec<(Self, usize)>, ()> {
+        parser::parse(s, notation)
+    }
+
+    pub fn sequence_to_string(sequence: &[(Self, usize)]) -> String {
+        let moves: Vec<String> = sequence.iter().map(|m| m.0.to_string()).collect();
+
+        moves.join(" ")
+    }
+
+    /// The sequence undoing `sequence`.
+    pub fn invert_sequence(sequence: &[(Self, usize)]) -> Vec<(Self, usize)> {
+        sequence.iter().rev()
+            .map(|&(m, power)| (Self::from_move(m.to_move(), 4 - power), 4 - power))
+            .collect()
+    }
+
+    /// Merge the turns of the same face, including across a turn of the
+    /// opposite face, and drop the ones cancelling out.
+    pub fn simplify_sequence(sequence: &[(Self, usize)]) -> Vec<(Self, usize)> {
+        let mut simplified: Vec<(Self, usize)> = Vec::new();
+
+        for &(m, power) in sequence {
+            let face = m.to_move() as usize;
+            let n = simplified.len();
+            let target = if n >= 1 && simplified[n - 1].0.to_move() as usize == face {
+                Some(n - 1)
+            } else if n >= 2 && simplified[n - 1].0.to_move() as usize == (face + 3) % 6
+                && simplified[n - 2].0.to_move() as usize == face {
+                Some(n - 2)
+            } else {
+                None
+            };
+
+            match target {
+                Some(i) => match (simplified[i].1 + power) % 4 {
+                    0 => { simplified.remove(i); },
+                    power => simplified[i] = (Self::from_move(m.to_move(), power), power),
+                },
+                None => simplified.push((m, power)),
+            }
+        }
+        simplified
+    }
+
+    /// Length of `sequence` in the quarter turn metric, half turns counting
+    /// as two.
+    pub fn quarter_turns(sequence: &[(Self, usize)]) -> usize {
+        sequence.iter().map(|&(_, power)| if power == 2 { 2 } else { 1 }).sum()
+    }
+
+    /// How many times `sequence` must be played to get back to the state it
+    /// started from.
+    pub fn sequence_order(sequence: &[(Self, usize)]) -> usize {
+        Cube::from_shuffle_sequence(sequence.to_vec()).order()
+    }
+
+    /// `length` random moves from `seed`, never turning the same face twice
+    /// in a row nor around a turn of the opposite face.
+    pub fn random_sequence(length: usize, seed: u64) -> Vec<(Self, usize)> {
+        let mut state = seed ^ 0x9e37_79b9_7f4a_7c15;
+        let mut faces: Vec<usize> = Vec::new();
         let mut sequence = Vec::new();
-        let splitted = s.split_whitespace();
 
+        while sequence.len() < length {
+            // xorshift64*
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            let random = state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32;
+            let (face, power) = ((random % 6) as usize, (random / 6 % 3) as usize + 1);
+
+            let n = faces.len();
+            if (n >= 1 && faces[n - 1] == face)
+                || (n >= 2 && faces[n - 1] == (face + 3) % 6 && faces[n - 2] == face) {
+                continue;
+            }
+            faces.push(face);
+            sequence.push((Self::from_move(Move::from_u(face), power), power));
+        }
+        sequence
+    }
+
+    pub fn from_move(m: Move, power: usize) -> Self {
         use self::UserMove::*;
-        for elem in splitted {
-            let move_ =  match elem {
-                "F" => (Front, 1),
-                "F'" => (FrontPrime, 3),
-                "F2" => (Front2, 2),
-                "R" => (Right, 1),
-                "R'" => (RightPrime, 3),
-                "R2" => (Right2, 2),
-                "U" => (Up, 1),
-                "U'" => (UpPrime, 3),
-                "U2" => (Up2, 2),
-                "B" => (Back, 1),
-                "B'" => (BackPrime, 3),
-                "B2" => (Back2, 2),
-                "L" => (Left, 1),
-                "L'" => (LeftPrime, 3),
-                "L2" => (Left2, 2),
-                "D" => (Down, 1),
-                "D'" => (DownPrime, 3),
-                "D2" => (Down2, 2),
-                _ => return Err(()),
-            };
-            sequence.push(move_)
+        match (m, power) {
+            (Move::Front, 1) => Front,
+            (Move::Front, 2) => Front2,
+            (Move::Front, _) => FrontPrime,
+            (Move::Right, 1) => Right,
+            (Move::Right, 2) => Right2,
+            (Move::Right, _) => RightPrime,
+            (Move::Up, 1) => Up,
+            (Move::Up, 2) => Up2,
+            (Move::Up, _) => UpPrime,
+            (Move::Back, 1) => Back,
+            (Move::Back, 2) => Back2,
+            (Move::Back, _) => BackPrime,
+            (Move::Left, 1) => Left,
+            (Move::Left, 2) => Left2,
+            (Move::Left, _) => LeftPrime,
+            (Move::Down, 1) => Down,
+            (Move::Down, 2) => Down2,
+            (Move::Down, _) => DownPrime,
+        }
+    }
+
+    /// Number of quarter turns clockwise: 1, 2, or 3 for a counterclockwise
+    /// turn.
+    pub fn power(&self) -> usize {
+        [1, 3, 2][*self as usize % 3]
+    }
+
+    /// The face the move turns.
+    pub fn face(&self) -> Face {
+        match self.to_move() {
+            Move::Up => Face::U,
+            Move::Right => Face::R,
+            Move::Front => Face::F,
+            Move::Down => Face::D,
+            Move::Left => Face::L,
+            Move::Back => Face::B,
         }
-        Ok(sequence)
     }
 
     pub fn to_move(&self) -> Move {
@@ -145,6 +298,18 @@ impl UserMove {
     }
 }
 
+impl Serialize for UserMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_name(serializer, &USER_MOVE_NAMES, *self as usize)
+    }
+}
+
+impl<'de> Deserialize<'de> for UserMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_name(deserializer, &USER_MOVE_NAMES).map(|index| USER_MOVES[index])
+    }
+}
+
 impl ToString for UserMove {
     fn to_string(&self) -> String {
         use self::UserMove::*;
@@ -0,0 +1,361 @@
+//! Parser of `UserMove::sequence_from_notation`.
+//!
+//! Besides moves, it expands:
+//! - `[A, B]` commutators into `A B A' B'`,
+//! - `[A: B]` conjugates into `A B A'`,
+//! - `(A)n` and `[..]n` repetitions into `A` played `n` times,
+//!
+//! and skips `// comments` until the end of the line and `/* comments */`.
+//! Sequences expanding to more than `MAX_MOVES` turns or nesting brackets
+//! deeper than `MAX_DEPTH` are rejected.
+//!
+//! Wide moves, slices and rotations turn the cube as a whole, so the moves
+//! are first read as `Turn`s, then played as face turns by `evaluate` with
+//! the centers staying in place.
+
+use move_::Move;
+use move_::Notation;
+use move_::UserMove;
+
+/// Most turns a sequence can expand to.
+const MAX_MOVES: usize = 100_000;
+
+/// Most brackets a sequence can nest.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Face,
+    /// Two layers, such as `Rw` or `r`.
+    Wide,
+    /// The middle layer along `face`: `M` along L, `E` along D and `S`
+    /// along F.
+    Slice,
+    /// The whole cube, as `face` turns: `x` as R, `y` as U and `z` as F.
+    Rotation,
+}
+
+/// A turn as written, its face being the one in front of the solver in the
+/// current orientation of the cube, in the order of `Move::from_u`.
+#[derive(Clone, Copy)]
+struct Turn {
+    kind: Kind,
+    face: usize,
+    /// Clockwise quarter turns, in [0, 4).
+    power: usize,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    /// Brackets open around `position`.
+    depth: usize,
+    notation: Notation,
+}
+
+pub fn parse(s: &str, notation: Notation) -> Result<Vec<(UserMove, usize)>, ()> {
+    let mut parser = Parser { chars: s.chars().collect(), position: 0, depth: 0, notation: notation };
+    let turns = parser.sequence(&[])?;
+
+    match parser.peek() {
+        None => Ok(evaluate(&turns)),
+        Some(_) => Err(()),
+    }
+}
+
+fn invert(turns: &[Turn]) -> Vec<Turn> {
+    turns.iter().rev().map(|turn| Turn { power: (4 - turn.power) % 4, ..*turn }).collect()
+}
+
+/// Turn the cube as a whole like `face` once: `orientation[f]` is the face
+/// of the cube now in front of the solver at `f`.
+fn rotate(orientation: &mut [usize; 6], face: usize) {
+    // Faces of the cycle of a turn of U, R and F, the one in front of the
+    // solver at each of them coming from the next one.
+    let cycle = match face % 3 {
+        0 => [2, 1, 5, 4],
+        1 => [0, 2, 3, 5],
+        _ => [0, 4, 3, 1],
+    };
+    let times = if face < 3 { 1 } else { 3 };
+
+    for _ in 0..times {
+        let first = orientation[cycle[0]];
+        for i in 0..3 {
+            orientation[cycle[i]] = orientation[cycle[i + 1]];
+        }
+        orientation[cycle[3]] = first;
+    }
+}
+
+/// The face turns playing `turns` with the centers in place.
+fn evaluate(turns: &[Turn]) -> Vec<(UserMove, usize)> {
+    let mut orientation = [0, 1, 2, 3, 4, 5];
+    let mut sequence = Vec::new();
+    let mut push = |face: usize, power: usize| {
+        if power % 4 != 0 {
+            sequence.push((UserMove::from_move(Move::from_u(face), power % 4), power % 4));
+        }
+    };
+
+    for turn in turns {
+        let opposite = (turn.face + 3) % 6;
+        match turn.kind {
+            Kind::Face => push(orientation[turn.face], turn.power),
+            Kind::Wide => push(orientation[opposite], turn.power),
+            Kind::Slice => {
+                push(orientation[opposite], turn.power);
+                push(orientation[turn.face], 4 - turn.power);
+            },
+            Kind::Rotation => { },
+        }
+        if turn.kind != Kind::Face {
+            for _ in 0..turn.power {
+                rotate(&mut orientation, turn.face);
+            }
+        }
+    }
+    sequence
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.position += 1;
+        c
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ()> {
+        if self.next() == Some(c) { Ok(()) } else { Err(()) }
+    }
+
+    /// Enter the bracket at `position`, up to `MAX_DEPTH` of them.
+    fn open(&mut self) -> Result<(), ()> {
+        self.position += 1;
+        self.depth += 1;
+        if self.depth > MAX_DEPTH { Err(()) } else { Ok(()) }
+    }
+
+    /// Skip whitespace and comments.
+    fn skip_blank(&mut self) -> Result<(), ()> {
+        loop {
+            match (self.peek(), self.chars.get(self.position + 1)) {
+                (Some(c), _) if c.is_whitespace() => self.position += 1,
+                (Some('/'), Some(&'/')) => {
+                    while self.peek().map_or(false, |c| c != '\n') {
+                        self.position += 1;
+                    }
+                },
+                (Some('/'), Some(&'*')) => {
+                    self.position += 2;
+                    while (self.peek(), self.chars.get(self.position + 1)) != (Some('*'), Some(&'/')) {
+                        self.next().ok_or(())?;
+                    }
+                    self.position += 2;
+                },
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Turns up to one of `closing`, left to be read by the caller, or to the
+    /// end of the input if `closing` is empty.
+    fn sequence(&mut self, closing: &[char]) -> Result<Vec<Turn>, ()> {
+        let mut sequence = Vec::new();
+
+        loop {
+            self.skip_blank()?;
+            let group = match self.peek() {
+                None if closing.is_empty() => return Ok(sequence),
+                Some(c) if closing.contains(&c) => return Ok(sequence),
+                Some('(') => {
+                    self.open()?;
+                    let group = self.sequence(&[')'])?;
+                    self.expect(')')?;
+                    self.depth -= 1;
+                    group
+                },
+                Some('[') => {
+                    self.open()?;
+                    let a = self.sequence(&[',', ':'])?;
+                    let separator = self.next();
+                    let b = self.sequence(&[']'])?;
+                    self.expect(']')?;
+                    self.depth -= 1;
+
+                    let mut group = a.clone();
+                    group.extend(b.iter().cloned());
+                    group.extend(invert(&a));
+                    if separator == Some(',') {
+                        group.extend(invert(&b));
+                    }
+                    group
+                },
+                Some(_) => vec![self.turn()?],
+                None => return Err(()),
+            };
+
+            let count = self.count()?;
+            match group.len().checked_mul(count).and_then(|length| length.checked_add(sequence.len())) {
+                Some(length) if length <= MAX_MOVES => { },
+                _ => return Err(()),
+            }
+            for _ in 0..count {
+                sequence.extend(group.iter().cloned());
+            }
+        }
+    }
+
+    /// Repetitions of the group just read: 1 unless a number follows a
+    /// closing bracket.
+    fn count(&mut self) -> Result<usize, ()> {
+        match self.chars.get(self.position.wrapping_sub(1)) {
+            Some(&')') | Some(&']') => { },
+            _ => return Ok(1),
+        }
+        let start = self.position;
+        while self.peek().map_or(false, |c| c == ' ' || c == '\t') {
+            self.position += 1;
+        }
+
+        match self.number()? {
+            Some(0) => Err(()),
+            Some(count) => Ok(count),
+            None => {
+                self.position = start;
+                Ok(1)
+            },
+        }
+    }
+
+    /// The number at `position`, if any, an error if it overflows.
+    fn number(&mut self) -> Result<Option<usize>, ()> {
+        let mut digits = String::new();
+
+        while let Some(c) = self.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            digits.push(c);
+            self.position += 1;
+        }
+        if digits.is_empty() {
+            return Ok(None);
+        }
+        digits.parse().map(Some).map_err(|_| ())
+    }
+
+    fn is_prime(c: Option<char>) -> bool {
+        c == Some('\'') || c == Some('’') || c == Some('′') || c == Some('i')
+    }
+
+    /// `R`, `R'` or `R2`, or in the lenient notation any of the turns of
+    /// `Notation::Lenient`.
+    fn turn(&mut self) -> Result<Turn, ()> {
+        let (kind, face) = match self.next().ok_or(())? {
+            c if "URFDLB".contains(c) => {
+                if self.notation == Notation::Lenient && self.peek() == Some('w') {
+                    self.position += 1;
+                    (Kind::Wide, "URFDLB".find(c).unwrap())
+                } else {
+                    (Kind::Face, "URFDLB".find(c).unwrap())
+                }
+            },
+            c if "urfdlb".contains(c) => (Kind::Wide, "urfdlb".find(c).unwrap()),
+            'M' => (Kind::Slice, 4),
+            'E' => (Kind::Slice, 3),
+            'S' => (Kind::Slice, 2),
+            'x' => (Kind::Rotation, 1),
+            'y' => (Kind::Rotation, 0),
+            'z' => (Kind::Rotation, 2),
+            _ => return Err(()),
+        };
+
+        match self.notation {
+            Notation::Strict => {
+                if kind != Kind::Face {
+                    return Err(());
+                }
+                let power = match self.peek() {
+                    Some('\'') => 3,
+                    Some('2') => 2,
+                    _ => 1,
+                };
+                if power != 1 {
+                    self.position += 1;
+                }
+                match self.peek() {
+                    None => { },
+                    Some(c) if c.is_whitespace() || "()[],:/".contains(c) => { },
+                    Some(_) => return Err(()),
+                }
+                Ok(Turn { kind: kind, face: face, power: power })
+            },
+            Notation::Lenient => {
+                // A number and a prime, in any order.
+                let mut prime = Self::is_prime(self.peek());
+                if prime {
+                    self.position += 1;
+                }
+                let power = self.number()?.unwrap_or(1) % 4;
+                if !prime && Self::is_prime(self.peek()) {
+                    self.position += 1;
+                    prime = true;
+                }
+                Ok(Turn { kind: kind, face: face, power: if prime { (4 - power) % 4 } else { power } })
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use algorithms::PLL;
+    use cube::Cube;
+    use move_::Notation;
+    use move_::UserMove;
+    use move_::parser::parse;
+
+    fn expand(s: &str) -> String {
+        UserMove::sequence_to_string(&parse(s, Notation::Lenient).unwrap())
+    }
+
+    #[test]
+    fn brackets() {
+        assert_eq!(expand("[R, U]"), "R U R' U'");
+        assert_eq!(expand("[R: U]"), "R U R'");
+        assert_eq!(expand("(R U)3"), "R U R U R U");
+        assert_eq!(expand("[F: [R, U]]"), "F R U R' U' F'");
+        assert_eq!(expand("R /* comment */ U // comment"), "R U");
+    }
+
+    #[test]
+    fn rotations_and_slices() {
+        assert_eq!(expand("x U x'"), "F");
+
+        let h_perm = PLL.iter().find(|case| case.name == "H perm").unwrap();
+        let slices = Cube::from_shuffle_sequence(parse("M2 U M2 U2 M2 U M2", Notation::Lenient).unwrap());
+        assert!(slices == Cube::from_shuffle_sequence(h_perm.sequence()));
+    }
+
+    #[test]
+    fn notations() {
+        assert_eq!(expand("R2' R'2 R3 Ri R’"), "R2 R2 R' R' R'");
+        assert!(parse("R U2 F'", Notation::Strict).is_ok());
+        assert!(parse("R U2' F'", Notation::Strict).is_err());
+        assert!(parse("r", Notation::Strict).is_err());
+    }
+
+    #[test]
+    fn limits() {
+        assert!(parse("((((R)100)100)100)100", Notation::Lenient).is_err());
+        assert!(parse(&format!("{}R{}", "(".repeat(60000), ")".repeat(60000)), Notation::Lenient).is_err());
+        assert!(parse("R99999999999999999999999", Notation::Lenient).is_err());
+        assert!(parse("(R)99999999999999999999999", Notation::Lenient).is_err());
+        assert!(parse("(R)0", Notation::Lenient).is_err());
+    }
+}
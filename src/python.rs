@@ -0,0 +1,230 @@
+//! Python extension module, built with the `python` feature:
+//!
+//! ```python
+//! import rubik
+//! cube = rubik.Cube(str(rubik.scramble(seed=42)))
+//! solution = rubik.solve(cube)
+//! print(solution, solution.htm)
+//! ```
+//!
+//! The tables are loaded once, on the first solve or by `load_tables`, and
+//! shared by every later call.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use coordinate::Coordinate;
+use coordinate::store::Directory;
+use cube::Cube;
+use cube::color::ColorScheme;
+use move_::UserMove;
+use render;
+use render::svg::Svg;
+use solver::Solver;
+
+/// Tables shared by all the solves.
+static TABLES: Mutex<Option<Arc<Coordinate>>> = Mutex::new(None);
+
+fn tables(cache_dir: &str) -> Arc<Coordinate> {
+    let mut tables = TABLES.lock().unwrap();
+
+    if tables.is_none() {
+        let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+        coordinate.init_pruning_from(&mut Directory::new(cache_dir));
+        *tables = Some(Arc::new(coordinate));
+    }
+    tables.as_ref().unwrap().clone()
+}
+
+fn scheme(name: &str) -> PyResult<ColorScheme> {
+    ColorScheme::from_name(name).map_err(PyValueError::new_err)
+}
+
+fn parse_sequence(s: &str) -> PyResult<Vec<(UserMove, usize)>> {
+    UserMove::sequence_from_str(s).map_err(|_| PyValueError::new_err(format!("invalid move sequence: {}", s)))
+}
+
+/// A sequence of moves, such as `R U R' U'`.
+#[pyclass(name = "Sequence", module = "rubik")]
+#[derive(Clone)]
+pub struct PySequence {
+    moves: Vec<(UserMove, usize)>,
+}
+
+#[pymethods]
+impl PySequence {
+    #[new]
+    #[pyo3(signature = (moves = ""))]
+    fn new(moves: &str) -> PyResult<Self> {
+        Ok(Self { moves: parse_sequence(moves)? })
+    }
+
+    fn inverse(&self) -> Self {
+        Self { moves: UserMove::invert_sequence(&self.moves) }
+    }
+
+    fn simplify(&self) -> Self {
+        Self { moves: UserMove::simplify_sequence(&self.moves) }
+    }
+
+    /// Length in the half turn metric.
+    #[getter]
+    fn htm(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Length in the quarter turn metric.
+    #[getter]
+    fn qtm(&self) -> usize {
+        UserMove::quarter_turns(&self.moves)
+    }
+
+    /// Each move, as a string.
+    fn moves(&self) -> Vec<String> {
+        self.moves.iter().map(|m| UserMove::sequence_to_string(&[*m])).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.moves.len()
+    }
+
+    fn __str__(&self) -> String {
+        UserMove::sequence_to_string(&self.moves)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Sequence({:?})", UserMove::sequence_to_string(&self.moves))
+    }
+
+    fn __add__(&self, other: &Self) -> Self {
+        Self { moves: self.moves.iter().chain(other.moves.iter()).cloned().collect() }
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        UserMove::sequence_to_string(&self.moves) == UserMove::sequence_to_string(&other.moves)
+    }
+}
+
+/// A cube state, solved by default, or the state a scramble leads to.
+#[pyclass(name = "Cube", module = "rubik")]
+#[derive(Clone)]
+pub struct PyCube {
+    cube: Cube,
+}
+
+#[pymethods]
+impl PyCube {
+    #[new]
+    #[pyo3(signature = (scramble = ""))]
+    fn new(scramble: &str) -> PyResult<Self> {
+        Ok(Self { cube: Cube::from_shuffle_sequence(parse_sequence(scramble)?) })
+    }
+
+    /// The cube with these 54 sticker colors, faces U, R, F, D, L and B, each
+    /// face row by row.
+    #[staticmethod]
+    #[pyo3(signature = (facelets, scheme = "western"))]
+    fn from_facelets(facelets: &str, scheme: &str) -> PyResult<Self> {
+        Cube::from_facelets(facelets, &self::scheme(scheme)?)
+            .map(|cube| Self { cube: cube })
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    #[pyo3(signature = (scheme = "western"))]
+    fn facelets(&self, scheme: &str) -> PyResult<String> {
+        Ok(self.cube.to_facelets(&self::scheme(scheme)?))
+    }
+
+    /// A copy of the cube with `moves` applied.
+    fn apply(&self, moves: &str) -> PyResult<Self> {
+        let mut cube = self.cube.clone();
+
+        cube.apply_sequence(parse_sequence(moves)?);
+        Ok(Self { cube: cube })
+    }
+
+    fn is_solved(&self) -> bool {
+        self.cube.is_solved()
+    }
+
+    /// An SVG drawing, `net` or `isometric`.
+    #[pyo3(signature = (view = "net", scheme = "western"))]
+    fn svg(&self, view: &str, scheme: &str) -> PyResult<String> {
+        let svg = Svg::new().scheme(self::scheme(scheme)?);
+
+        match view {
+            "net" => Ok(svg.net(&self.cube)),
+            "isometric" => Ok(svg.isometric(&self.cube)),
+            _ => Err(PyValueError::new_err(format!("unknown view {}", view))),
+        }
+    }
+
+    /// Shown by notebooks.
+    fn _repr_svg_(&self) -> String {
+        Svg::new().net(&self.cube)
+    }
+
+    fn __str__(&self) -> String {
+        render::render(&self.cube, &render::Letters)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.cube == other.cube
+    }
+}
+
+/// Load the tables from the `cache_dir` folder, or generate and cache them
+/// there, unless they are loaded already. Solving loads them from
+/// `pruning_tables` otherwise.
+#[pyfunction]
+#[pyo3(signature = (cache_dir = "pruning_tables"))]
+fn load_tables(py: Python, cache_dir: &str) {
+    py.allow_threads(|| { tables(cache_dir); });
+}
+
+/// A sequence solving `cube` with the two-phase algorithm, in at most
+/// `max_depth` moves, giving up after `timeout` seconds if given.
+#[pyfunction]
+#[pyo3(signature = (cube, max_depth = 26, timeout = None))]
+fn solve(py: Python, cube: &PyCube, max_depth: u8, timeout: Option<f64>) -> PyResult<PySequence> {
+    let coordinate = py.allow_threads(|| tables("pruning_tables"));
+    let mut solver = Solver::new(&coordinate).max_depth(max_depth);
+    if let Some(timeout) = timeout {
+        solver = solver.timeout(Duration::from_millis((timeout * 1000.0) as u64));
+    }
+
+    match py.allow_threads(|| solver.search(&cube.cube).0) {
+        Ok(phases) => Ok(PySequence { moves: phases.concat() }),
+        Err(failure) => Err(PyRuntimeError::new_err(failure.to_string())),
+    }
+}
+
+/// A random scramble of `length` moves, from the current time unless a
+/// `seed` is given.
+#[pyfunction]
+#[pyo3(signature = (length = 25, seed = None))]
+fn scramble(length: usize, seed: Option<u64>) -> PySequence {
+    let seed = seed.unwrap_or_else(|| {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        now.as_secs() ^ u64::from(now.subsec_nanos())
+    });
+
+    PySequence { moves: UserMove::random_sequence(length, seed) }
+}
+
+#[pymodule]
+fn rubik(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<PyCube>()?;
+    m.add_class::<PySequence>()?;
+    m.add_function(wrap_pyfunction!(self::load_tables, m)?)?;
+    m.add_function(wrap_pyfunction!(self::solve, m)?)?;
+    m.add_function(wrap_pyfunction!(self::scramble, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,226 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub mod playback;
+pub mod svg;
+
+use std::env;
+use std::io;
+use std::io::Write;
+
+use cube::Cube;
+use cube::color::ColorScheme;
+use cube::face::Face;
+
+/// Draws the stickers of a cube net as text.
+pub trait Renderer {
+    /// Text of one sticker.
+    fn sticker(&self, face: Face) -> String;
+
+    /// Text of one sticker of the face which just turned, as wide as the
+    /// other ones.
+    fn highlighted(&self, face: Face) -> String;
+
+    /// Number of columns one sticker takes on screen.
+    fn width(&self) -> usize;
+}
+
+/// Plain ASCII letters of the faces, for logs and files.
+pub struct Letters;
+
+/// Unicode full blocks, colored with the 16 standard ANSI colors.
+pub struct Blocks {
+    pub scheme: ColorScheme,
+}
+
+/// Letters on the 16 standard ANSI colors.
+pub struct Ansi {
+    pub scheme: ColorScheme,
+}
+
+/// Letters on the 256 colors palette.
+pub struct Ansi256 {
+    pub scheme: ColorScheme,
+}
+
+/// Letters on 24-bit colors.
+pub struct TrueColor {
+    pub scheme: ColorScheme,
+}
+
+impl Renderer for Letters {
+    fn sticker(&self, face: Face) -> String {
+        format!(" {} ", face.to_string())
+    }
+
+    fn highlighted(&self, face: Face) -> String {
+        format!("[{}]", face.to_string())
+    }
+
+    fn width(&self) -> usize {
+        3
+    }
+}
+
+impl Renderer for Blocks {
+    fn sticker(&self, face: Face) -> String {
+        format!("\x1b[{}m\u{2588}\u{2588}\x1b[0m", self.scheme.color(face).ansi)
+    }
+
+    /// Dark shade blocks instead of full ones.
+    fn highlighted(&self, face: Face) -> String {
+        format!("\x1b[{}m\u{2593}\u{2593}\x1b[0m", self.scheme.color(face).ansi)
+    }
+
+    fn width(&self) -> usize {
+        2
+    }
+}
+
+impl Renderer for Ansi {
+    fn sticker(&self, face: Face) -> String {
+        format!("\x1b[7;{}m {} \x1b[0m", self.scheme.color(face).ansi, face.to_string())
+    }
+
+    fn highlighted(&self, face: Face) -> String {
+        format!("\x1b[7;{}m[{}]\x1b[0m", self.scheme.color(face).ansi, face.to_string())
+    }
+
+    fn width(&self) -> usize {
+        3
+    }
+}
+
+impl Renderer for Ansi256 {
+    fn sticker(&self, face: Face) -> String {
+        let color = self.scheme.color(face);
+        let text = if color.is_light() { 16 } else { 231 };
+        format!("\x1b[48;5;{};38;5;{}m {} \x1b[0m", color.ansi256(), text, face.to_string())
+    }
+
+    fn highlighted(&self, face: Face) -> String {
+        let color = self.scheme.color(face);
+        let text = if color.is_light() { 16 } else { 231 };
+        format!("\x1b[48;5;{};38;5;{}m[{}]\x1b[0m", color.ansi256(), text, face.to_string())
+    }
+
+    fn width(&self) -> usize {
+        3
+    }
+}
+
+impl Renderer for TrueColor {
+    fn sticker(&self, face: Face) -> String {
+        let color = self.scheme.color(face);
+        let (r, g, b) = color.rgb;
+        let text = if color.is_light() { 0 } else { 255 };
+        format!("\x1b[48;2;{};{};{};38;2;{t};{t};{t}m {} \x1b[0m", r, g, b, face.to_string(), t = text)
+    }
+
+    fn highlighted(&self, face: Face) -> String {
+        let color = self.scheme.color(face);
+        let (r, g, b) = color.rgb;
+        let text = if color.is_light() { 0 } else { 255 };
+        format!("\x1b[48;2;{};{};{};38;2;{t};{t};{t}m[{}]\x1b[0m", r, g, b, face.to_string(), t = text)
+    }
+
+    fn width(&self) -> usize {
+        3
+    }
+}
+
+#[cfg(unix)]
+fn stdout_is_terminal() -> bool {
+    extern "C" {
+        fn isatty(fd: i32) -> i32;
+    }
+    unsafe { isatty(1) == 1 }
+}
+
+#[cfg(not(unix))]
+fn stdout_is_terminal() -> bool {
+    true
+}
+
+/// `Letters` when stdout is not a terminal or when the `NO_COLOR`
+/// environment variable is set to anything but an empty string, `Ansi` with
+/// the scheme of `ColorScheme::from_env` otherwise.
+pub fn from_env() -> Box<Renderer> {
+    match env::var("NO_COLOR") {
+        Ok(ref value) if !value.is_empty() => Box::new(Letters),
+        _ if !stdout_is_terminal() => Box::new(Letters),
+        _ => Box::new(Ansi { scheme: ColorScheme::from_env() }),
+    }
+}
+
+/// The renderer called `name`: `letters`, `blocks`, `ansi`, `ansi256` or
+/// `truecolor`, in the scheme of `ColorScheme::from_env`, or `auto` for the
+/// one of `from_env`.
+pub fn from_name(name: &str) -> Option<Box<Renderer>> {
+    let scheme = ColorScheme::from_env();
+
+    match name {
+        "auto" => Some(from_env()),
+        "letters" => Some(Box::new(Letters)),
+        "blocks" => Some(Box::new(Blocks { scheme: scheme })),
+        "ansi" => Some(Box::new(Ansi { scheme: scheme })),
+        "ansi256" => Some(Box::new(Ansi256 { scheme: scheme })),
+        "truecolor" => Some(Box::new(TrueColor { scheme: scheme })),
+        _ => None,
+    }
+}
+
+/// Write the unfolded net of `cube`: U on top, then L, F, R and B, then D.
+pub fn write<W: Write>(cube: &Cube, renderer: &Renderer, out: &mut W) -> io::Result<()> {
+    write_highlighted(cube, renderer, None, out)
+}
+
+/// Same as `write`, with the stickers of `highlighted` drawn by
+/// `Renderer::highlighted`.
+pub fn write_highlighted<W: Write>(cube: &Cube, renderer: &Renderer, highlighted: Option<Face>,
+                                   out: &mut W) -> io::Result<()> {
+    let order = [Face::U, Face::L, Face::F, Face::R, Face::B, Face::D];
+    let faces: Vec<Vec<String>> = order.iter()
+        .map(|&face| {
+            cube.face(face).iter()
+                .map(|&sticker| {
+                    if highlighted == Some(face) { renderer.highlighted(sticker) } else { renderer.sticker(sticker) }
+                })
+                .collect()
+        })
+        .collect();
+    let indent = " ".repeat(3 * renderer.width() + 1);
+
+    writeln!(out)?;
+    for y in 0..3 {
+        write!(out, "{}", indent)?;
+        for x in 0..3 {
+            write!(out, "{}", faces[0][x + y * 3])?;
+        }
+        writeln!(out)?;
+    }
+    writeln!(out)?;
+    for y in 0..3 {
+        for face in &faces[1..5] {
+            for x in 0..3 {
+                write!(out, "{}", face[x + y * 3])?;
+            }
+            write!(out, " ")?;
+        }
+        writeln!(out)?;
+    }
+    writeln!(out)?;
+    for y in 0..3 {
+        write!(out, "{}", indent)?;
+        for x in 0..3 {
+            write!(out, "{}", faces[5][x + y * 3])?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+pub fn render(cube: &Cube, renderer: &Renderer) -> String {
+    let mut out = Vec::new();
+
+    write(cube, renderer, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
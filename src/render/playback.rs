@@ -0,0 +1,89 @@
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+use cube::Cube;
+use move_::UserMove;
+use render;
+use render::Renderer;
+
+/// Plays sequences move by move, redrawing the net after each of them.
+pub struct Playback {
+    delay: Duration,
+    step: bool,
+}
+
+impl Playback {
+    pub fn new() -> Self {
+        Self {
+            delay: Duration::from_millis(500),
+            step: false,
+        }
+    }
+
+    /// Time each frame stays on screen.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Wait for Enter on stdin before each move instead of sleeping.
+    /// Entering `q` stops the playback.
+    pub fn step(mut self, step: bool) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Play `scramble` from the solved cube, then `solution`.
+    pub fn play<W: Write>(&self, renderer: &Renderer, scramble: &[(UserMove, usize)],
+                          solution: &[(UserMove, usize)], out: &mut W) -> io::Result<()> {
+        let mut cube = Cube::new_default();
+
+        self.draw(&cube, renderer, "Scramble", scramble, None, out)?;
+        for (title, moves) in vec![("Scramble", scramble), ("Solution", solution)] {
+            for (i, &m) in moves.iter().enumerate() {
+                if !self.wait()? {
+                    return Ok(());
+                }
+                cube.apply_sequence(Some(m));
+                self.draw(&cube, renderer, title, moves, Some(i), out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns false if the user asked to stop.
+    fn wait(&self) -> io::Result<bool> {
+        if !self.step {
+            thread::sleep(self.delay);
+            return Ok(true);
+        }
+
+        let stdin = io::stdin();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(false);
+        }
+        Ok(line.trim() != "q")
+    }
+
+    /// Clear the screen and draw the sequence, with its move `current` in
+    /// brackets, above the net, with the face `current` turned highlighted.
+    fn draw<W: Write>(&self, cube: &Cube, renderer: &Renderer, title: &str, moves: &[(UserMove, usize)],
+                      current: Option<usize>, out: &mut W) -> io::Result<()> {
+        let names: Vec<String> = moves.iter().enumerate()
+            .map(|(i, m)| if Some(i) == current { format!("[{}]", m.0.to_string()) } else { m.0.to_string() })
+            .collect();
+
+        write!(out, "\x1b[H\x1b[2J")?;
+        writeln!(out, "{}: {}", title, names.join(" "))?;
+        render::write_highlighted(cube, renderer, current.map(|i| moves[i].0.face()), out)?;
+        if self.step {
+            writeln!(out)?;
+            write!(out, "Enter: next move, q: quit ")?;
+        }
+        out.flush()
+    }
+}
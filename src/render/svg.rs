@@ -0,0 +1,175 @@
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use cube::Cube;
+use cube::color::ColorScheme;
+use cube::face::Face;
+use move_::UserMove;
+
+/// Exports cube diagrams as standalone SVG documents.
+pub struct Svg {
+    size: f64,
+    scheme: ColorScheme,
+    next_move: Option<UserMove>,
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Self {
+            size: 30.0,
+            scheme: ColorScheme::western(),
+            next_move: None,
+        }
+    }
+
+    /// Side of one sticker, in pixels.
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn scheme(mut self, scheme: ColorScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Draw an arrow showing `next_move` on the face it turns.
+    pub fn next_move(mut self, next_move: UserMove) -> Self {
+        self.next_move = Some(next_move);
+        self
+    }
+
+    /// The unfolded net, laid out like `Cube::print`.
+    pub fn net(&self, cube: &Cube) -> String {
+        let s = self.size;
+        let gap = s / 6.0;
+        let origin = |face: Face| -> (f64, f64) {
+            let (column, row) = match face {
+                Face::U => (1.0, 0.0),
+                Face::L => (0.0, 1.0),
+                Face::F => (1.0, 1.0),
+                Face::R => (2.0, 1.0),
+                Face::B => (3.0, 1.0),
+                Face::D => (1.0, 2.0),
+            };
+            (gap + column * (3.0 * s + gap), gap + row * (3.0 * s + gap))
+        };
+        let width = 4.0 * (3.0 * s + gap) + gap;
+        let height = 3.0 * (3.0 * s + gap) + gap;
+
+        let mut svg = Self::header(width, height);
+        for &face in &[Face::U, Face::L, Face::F, Face::R, Face::B, Face::D] {
+            let (x0, y0) = origin(face);
+            let point = |u: f64, v: f64| (x0 + u * s, y0 + v * s);
+            self.draw_face(&mut svg, cube, face, &point);
+        }
+        if let Some(m) = self.next_move {
+            let face = m.face();
+            let (x0, y0) = origin(face);
+            self.draw_arrow(&mut svg, m, &|u: f64, v: f64| (x0 + u * s, y0 + v * s));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// An isometric view of the U, F and R faces.
+    ///
+    /// Arrows are only drawn for moves of these three faces.
+    pub fn isometric(&self, cube: &Cube) -> String {
+        let s = self.size;
+        let (cos, sin) = ((PI / 6.0).cos(), (PI / 6.0).sin());
+        let margin = s / 2.0;
+        let width = 6.0 * cos * s + 2.0 * margin;
+        let height = 6.0 * s + 2.0 * margin;
+        // X goes to the right, Y towards the viewer and Z up, the cube
+        // filling [0, 3] on every axis.
+        let project = move |x: f64, y: f64, z: f64| {
+            (margin + (3.0 + x - y) * cos * s, margin + 3.0 * s + ((x + y) * sin - z) * s)
+        };
+
+        let mut svg = Self::header(width, height);
+        for &face in &[Face::U, Face::F, Face::R] {
+            self.draw_face(&mut svg, cube, face, &|u, v| Self::iso_point(&project, face, u, v));
+        }
+        if let Some(m) = self.next_move {
+            let face = m.face();
+            if face == Face::U || face == Face::F || face == Face::R {
+                self.draw_arrow(&mut svg, m, &|u, v| Self::iso_point(&project, face, u, v));
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Position of the point `(u, v)` of a visible face, `u` going right and
+    /// `v` going down when facing it.
+    fn iso_point<P>(project: &P, face: Face, u: f64, v: f64) -> (f64, f64)
+        where P: Fn(f64, f64, f64) -> (f64, f64)
+    {
+        match face {
+            Face::U => project(u, v, 3.0),
+            Face::F => project(u, 3.0, 3.0 - v),
+            _ => project(3.0, 3.0 - u, 3.0 - v),
+        }
+    }
+
+    fn header(width: f64, height: f64) -> String {
+        format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.1} {:.1}\">\n",
+                width.ceil(), height.ceil(), width, height)
+    }
+
+    fn draw_face<P>(&self, svg: &mut String, cube: &Cube, face: Face, point: &P)
+        where P: Fn(f64, f64) -> (f64, f64)
+    {
+        let stickers = cube.face(face);
+
+        for (i, &sticker) in stickers.iter().enumerate() {
+            let (u, v) = ((i % 3) as f64, (i / 3) as f64);
+            let corners = [point(u, v), point(u + 1.0, v), point(u + 1.0, v + 1.0), point(u, v + 1.0)];
+            let points: Vec<String> = corners.iter().map(|&(x, y)| format!("{:.1},{:.1}", x, y)).collect();
+
+            let _ = writeln!(svg, "  <polygon points=\"{}\" fill=\"{}\" stroke=\"#000\" stroke-width=\"{:.1}\"/>",
+                             points.join(" "), self.scheme.color(sticker).hex(), self.size / 15.0);
+        }
+    }
+
+    /// A circular arrow around the center of the face, clockwise when
+    /// facing it for a quarter turn, counterclockwise for a prime one, and
+    /// with a head on both ends for a half turn.
+    fn draw_arrow<P>(&self, svg: &mut String, m: UserMove, point: &P)
+        where P: Fn(f64, f64) -> (f64, f64)
+    {
+        let (start, end, heads): (f64, f64, &[f64]) = match m.power() {
+            1 => (-0.75 * PI, 0.5 * PI, &[1.0]),
+            3 => (0.5 * PI, -0.75 * PI, &[1.0]),
+            _ => (-0.75 * PI, 0.5 * PI, &[0.0, 1.0]),
+        };
+        let on_arc = |t: f64| {
+            let angle = start + (end - start) * t;
+            point(1.5 + angle.cos(), 1.5 + angle.sin())
+        };
+
+        let points: Vec<String> = (0..=24).map(|x| on_arc(x as f64 / 24.0))
+            .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+            .collect();
+        let _ = writeln!(svg, "  <polyline points=\"{}\" fill=\"none\" stroke=\"#333\" stroke-width=\"{:.1}\" stroke-linecap=\"round\"/>",
+                         points.join(" "), self.size / 6.0);
+
+        for &t in heads {
+            // The head points along the arc, away from its other end.
+            let angle = start + (end - start) * t;
+            let clockwise = (end > start) == (t == 1.0);
+            let sign = if clockwise { 1.0 } else { -1.0 };
+            let (tangent_u, tangent_v) = (-angle.sin() * sign, angle.cos() * sign);
+            let (tip_u, tip_v) = (1.5 + angle.cos() + 0.35 * tangent_u, 1.5 + angle.sin() + 0.35 * tangent_v);
+            let (side_u, side_v) = (angle.cos() * 0.25, angle.sin() * 0.25);
+            let head = [
+                point(tip_u, tip_v),
+                point(1.5 + angle.cos() + side_u, 1.5 + angle.sin() + side_v),
+                point(1.5 + angle.cos() - side_u, 1.5 + angle.sin() - side_v),
+            ];
+            let points: Vec<String> = head.iter().map(|&(x, y)| format!("{:.1},{:.1}", x, y)).collect();
+            let _ = writeln!(svg, "  <polygon points=\"{}\" fill=\"#333\"/>", points.join(" "));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Helpers for the serde implementations of cubes and moves: names such as
+//! `URF` or `R'` in human readable formats, indices in binary ones.
+
+use std::fmt;
+
+use serde::Deserializer;
+use serde::Serializer;
+use serde::de;
+use serde::de::Unexpected;
+use serde::de::Visitor;
+
+/// `names[index]` in human readable formats, `index` as a byte otherwise.
+pub fn serialize_name<S: Serializer>(serializer: S, names: &[&str], index: usize) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(names[index])
+    } else {
+        serializer.serialize_u8(index as u8)
+    }
+}
+
+/// Index in `names` of what `serialize_name` wrote.
+pub fn deserialize_name<'de, D: Deserializer<'de>>(deserializer: D, names: &'static [&'static str])
+                                                    -> Result<usize, D::Error> {
+    struct NameVisitor(&'static [&'static str]);
+
+    impl<'de> Visitor<'de> for NameVisitor {
+        type Value = usize;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "one of {} or its index", self.0.join(", "))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<usize, E> {
+            self.0.iter().position(|&name| name == v).ok_or_else(|| E::unknown_variant(v, self.0))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<usize, E> {
+            if v < self.0.len() as u64 {
+                Ok(v as usize)
+            } else {
+                Err(E::invalid_value(Unexpected::Unsigned(v), &self))
+            }
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(NameVisitor(names))
+    } else {
+        deserializer.deserialize_u8(NameVisitor(names))
+    }
+}
+
+/// For solutions and other move sequences, with
+/// `#[serde(with = "rubik::serialization::sequence")]`: `R U R' U'` in
+/// human readable formats, one byte per move otherwise.
+pub mod sequence {
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+    use serde::de;
+
+    use move_::UserMove;
+
+    pub fn serialize<S: Serializer>(sequence: &[(UserMove, usize)], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&UserMove::sequence_to_string(sequence))
+        } else {
+            let moves: Vec<UserMove> = sequence.iter().map(|&(m, _)| m).collect();
+            moves.serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(UserMove, usize)>, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            UserMove::sequence_from_str(&s).map_err(|_| de::Error::custom(format!("invalid move sequence: {}", s)))
+        } else {
+            let moves = Vec::<UserMove>::deserialize(deserializer)?;
+            Ok(moves.into_iter().map(|m| (m, m.power())).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bincode;
+    use serde_json;
+
+    use cube::Cube;
+    use cube::color::ColorScheme;
+    use move_::UserMove;
+
+    #[derive(Serialize, Deserialize)]
+    struct Solution {
+        #[serde(with = "::serialization::sequence")]
+        moves: Vec<(UserMove, usize)>,
+    }
+
+    fn solution() -> Solution {
+        Solution { moves: UserMove::sequence_from_str("R U' F2 L' D B2").unwrap() }
+    }
+
+    #[test]
+    fn human_readable_round_trip() {
+        let cube = Cube::random(1);
+        let json = serde_json::to_string(&cube).unwrap();
+        assert_eq!(json, format!("\"{}\"", cube.to_facelets(&ColorScheme::western())));
+        assert!(serde_json::from_str::<Cube>(&json).unwrap() == cube);
+
+        let json = serde_json::to_string(&solution()).unwrap();
+        assert_eq!(json, "{\"moves\":\"R U' F2 L' D B2\"}");
+        let moves = serde_json::from_str::<Solution>(&json).unwrap().moves;
+        assert_eq!(UserMove::sequence_to_string(&moves), "R U' F2 L' D B2");
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let cube = Cube::random(1);
+        let bytes = bincode::serialize(&cube, bincode::Infinite).unwrap();
+        assert!(bincode::deserialize::<Cube>(&bytes).unwrap() == cube);
+
+        let bytes = bincode::serialize(&solution(), bincode::Infinite).unwrap();
+        let moves = bincode::deserialize::<Solution>(&bytes).unwrap().moves;
+        assert_eq!(UserMove::sequence_to_string(&moves), "R U' F2 L' D B2");
+    }
+
+    #[test]
+    fn illegal_cube() {
+        // Twist the first corner, the orientations following the 8 corners.
+        let mut bytes = bincode::serialize(&Cube::new_default(), bincode::Infinite).unwrap();
+        bytes[8] = 1;
+        assert!(bincode::deserialize::<Cube>(&bytes).is_err());
+
+        // Swap two stickers of the U and R faces.
+        let mut facelets: Vec<char> = Cube::new_default().to_facelets(&ColorScheme::western()).chars().collect();
+        facelets.swap(8, 9);
+        let json = format!("\"{}\"", facelets.into_iter().collect::<String>());
+        assert!(serde_json::from_str::<Cube>(&json).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use algorithms::recognition;
+use cube::Cube;
+use cube::corners::Corner;
+use cube::edges::Edge;
+use move_::UserMove;
+use solver::partial::Goal;
+use solver::partial::PartialSolver;
+
+/// The four F2L pairs, each made of a D corner and a middle layer edge.
+const PAIRS: [(Corner, Edge); 4] = [
+    (Corner::DFR, Edge::FR),
+    (Corner::DLF, Edge::FL),
+    (Corner::DBL, Edge::BL),
+    (Corner::DRB, Edge::BR),
+];
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Stage {
+    Cross,
+    /// Number of the pair, from 1 to 4, in solving order.
+    F2l(usize),
+    Oll,
+    Pll,
+}
+
+impl ToString for Stage {
+    fn to_string(&self) -> String {
+        match *self {
+            Stage::Cross => "Cross".to_string(),
+            Stage::F2l(n) => format!("F2L {}", n),
+            Stage::Oll => "OLL".to_string(),
+            Stage::Pll => "PLL".to_string(),
+        }
+    }
+}
+
+pub struct Step {
+    pub stage: Stage,
+    pub case: String,
+    pub moves: Vec<(UserMove, usize)>,
+}
+
+/// Layer by layer solver giving a solution a human can follow: the cross on
+/// D, the four F2L pairs, then one OLL and one PLL algorithm.
+pub struct CfopSolver {
+    /// Indexed by the bit set of the pairs to have solved on top of the
+    /// cross, the first one solving the cross alone.
+    pairs: Vec<PartialSolver>,
+}
+
+impl CfopSolver {
+    pub fn new() -> Self {
+        let mut pairs = Vec::new();
+
+        for set in 0..1 << PAIRS.len() {
+            pairs.push(PartialSolver::new(Self::pairs_goal(set)));
+        }
+        Self {
+            pairs: pairs,
+        }
+    }
+
+    fn pairs_goal(set: usize) -> Goal {
+        let mut goal = Goal::cross();
+
+        for (x, &(corner, edge)) in PAIRS.iter().enumerate() {
+            if set & (1 << x) != 0 {
+                goal = goal.add(&Goal::f2l_pair(corner, edge));
+            }
+        }
+        goal
+    }
+
+    /// The steps solving `cube`, or `None` if the cross or a pair takes more
+    /// moves than the partial solvers search.
+    pub fn solve(&self, cube: &Cube) -> Option<Vec<Step>> {
+        let mut cube = cube.clone();
+        let mut steps = Vec::new();
+
+        let moves = self.pairs[0].solve(&cube)?;
+        cube.apply_sequence(moves.iter().cloned());
+        steps.push(Step { stage: Stage::Cross, case: "Cross".to_string(), moves: moves });
+
+        // Always insert the pair which is the quickest to solve next.
+        let mut solved = 0;
+        for n in 1..=PAIRS.len() {
+            let mut candidates = Vec::new();
+            for x in (0..PAIRS.len()).filter(|&x| solved & (1 << x) == 0) {
+                candidates.push((x, self.pairs[solved | 1 << x].solve(&cube)?));
+            }
+            let (pair, moves) = candidates.into_iter()
+                .min_by_key(|&(_, ref moves)| moves.len())
+                .unwrap();
+            let (corner, edge) = PAIRS[pair];
+
+            solved |= 1 << pair;
+            cube.apply_sequence(moves.iter().cloned());
+            steps.push(Step {
+                stage: Stage::F2l(n),
+                case: format!("{}/{} pair", corner.to_string(), edge.to_string()),
+                moves: moves,
+            });
+        }
+
+        let (case, moves) = match recognition::recognize_oll(&cube) {
+            Some(oll) => (oll.case.name.to_string(), oll.moves()),
+            None => ("Skip".to_string(), Vec::new()),
+        };
+        cube.apply_sequence(moves.iter().cloned());
+        steps.push(Step { stage: Stage::Oll, case: case, moves: moves });
+
+        let (case, moves) = match recognition::recognize_pll(&cube) {
+            Some(pll) => (pll.case.name.to_string(), pll.moves()),
+            None => ("Skip".to_string(), Self::auf(&cube)),
+        };
+        steps.push(Step { stage: Stage::Pll, case: case, moves: moves });
+        Some(steps)
+    }
+
+    /// U turn left to solve a cube whose last layer only needs one.
+    fn auf(cube: &Cube) -> Vec<(UserMove, usize)> {
+        for n in 0..4 {
+            let mut turned = cube.clone();
+            turned.apply_sequence(vec![(UserMove::Up, n)]);
+            if turned.is_solved() {
+                return match n {
+                    0 => vec![],
+                    _ => vec![(UserMove::from_move(UserMove::Up.to_move(), n), n)],
+                };
+            }
+        }
+        unreachable!()
+    }
+}
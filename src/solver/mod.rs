@@ -1,20 +1,288 @@
+pub mod partial;
+pub mod cfop;
+pub mod thistlethwaite;
+
+use std::time::Duration;
+use std::time::Instant;
+
 use cube::Cube;
+use coordinate::Coordinate;
+use move_::Move;
+use move_::UserMove;
 
-pub struct Solver {
+/// Moves which are not allowed in phase 2: quarter turns of R, F, L and B.
+const PHASE2_FORBIDDEN: [usize; 8] = [3, 5, 6, 8, 12, 14, 15, 17];
+
+pub struct Solver<'a> {
     max_depth: u8,
-    cube: Cube,
+    timeout: Option<Duration>,
+    coordinate: &'a Coordinate,
+}
+
+/// State of one run of the two-phase search.
+struct Search {
+    faces: Vec<usize>,
+    powers: Vec<usize>,
+    urf_to_dlf: u32,
+    fr_to_br: u32,
+    ur_to_ul: u32,
+    ub_to_df: u32,
+    parity: u32,
+    deadline: Option<Instant>,
+    /// Number of moves of phase 1 in the solution found.
+    phase1_length: usize,
+    /// Number of nodes visited in both phases.
+    nodes: u64,
+    timed_out: bool,
+}
+
+impl Search {
+    /// Whether the deadline has passed, which stops the search.
+    fn timed_out(&mut self) -> bool {
+        if !self.timed_out && self.deadline.map_or(false, |deadline| Instant::now() > deadline) {
+            self.timed_out = true;
+        }
+        self.timed_out
+    }
+}
+
+/// Why a search found no solution.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Failure {
+    /// There is none within this maximum depth.
+    TooLong(u8),
+    TimedOut,
+}
+
+impl ToString for Failure {
+    fn to_string(&self) -> String {
+        match *self {
+            Failure::TooLong(max_depth) => format!("no solution within {} moves", max_depth),
+            Failure::TimedOut => "timed out".to_string(),
+        }
+    }
 }
 
-impl Solver {
-    pub fn new(cube: Cube) -> Self {
+impl<'a> Solver<'a> {
+    /// `coordinate` must have gone through `Coordinate::init_pruning`.
+    pub fn new(coordinate: &'a Coordinate) -> Self {
         Self {
             max_depth: 26,
-            cube: cube,
+            timeout: None,
+            coordinate: coordinate,
+        }
+    }
+
+    /// Longest solution to look for, in moves.
+    pub fn max_depth(mut self, max_depth: u8) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Give up searching after `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Find a sequence bringing `cube` back to the solved state.
+    ///
+    /// Returns `None` if there is none within the maximum depth, or if the
+    /// timeout is reached first.
+    pub fn solve(&self, cube: &Cube) -> Option<Vec<(UserMove, usize)>> {
+        self.solve_phases(cube).map(|phases| phases.concat())
+    }
+
+    /// The moves of phase 1, reaching `<U, D, R2, L2, F2, B2>`, and of
+    /// phase 2.
+    pub fn solve_phases(&self, cube: &Cube) -> Option<Vec<Vec<(UserMove, usize)>>> {
+        self.search(cube).0.ok()
+    }
+
+    /// Same as `solve_phases`, telling why no solution was found, with the
+    /// number of nodes the search visited.
+    pub fn search(&self, cube: &Cube) -> (Result<Vec<Vec<(UserMove, usize)>>, Failure>, u64) {
+        let mut search = Search {
+            faces: Vec::new(),
+            powers: Vec::new(),
+            urf_to_dlf: cube.urf_to_dlf(),
+            fr_to_br: cube.fr_to_br(),
+            ur_to_ul: cube.ur_to_ul(),
+            ub_to_df: cube.ub_to_df(),
+            parity: cube.corner_parity(),
+            deadline: self.timeout.map(|timeout| Instant::now() + timeout),
+            phase1_length: 0,
+            nodes: 0,
+            timed_out: false,
+        };
+        let (twist, flip, slice) = (cube.twist(), cube.flip(), cube.fr_to_br() / 24);
+
+        for depth in 0..=self.max_depth as usize {
+            if self.phase1(&mut search, twist, flip, slice, depth) {
+                let moves: Vec<(UserMove, usize)> = search.faces.iter().zip(search.powers.iter())
+                    .map(|(&face, &power)| (UserMove::from_move(Move::from_u(face), power), power))
+                    .collect();
+                let (phase1, phase2) = moves.split_at(search.phase1_length);
+                return (Ok(vec![phase1.to_vec(), phase2.to_vec()]), search.nodes);
+            }
+            if search.timed_out {
+                return (Err(Failure::TimedOut), search.nodes);
+            }
+        }
+        (Err(Failure::TooLong(self.max_depth)), search.nodes)
+    }
+
+    /// Find a sequence transforming `start` into `target`.
+    ///
+    /// Solving `target^-1 * start` gives the moves `X` such that
+    /// `target^-1 * start * X` is solved, hence `start * X == target`.
+    pub fn solve_to(&self, start: &Cube, target: &Cube) -> Option<Vec<(UserMove, usize)>> {
+        let mut cube = target.inverse();
+
+        cube.multiply_by(start);
+        self.solve(&cube)
+    }
+
+    fn phase1(&self, search: &mut Search, twist: u32, flip: u32, slice: u32, depth: usize) -> bool {
+        search.nodes += 1;
+        if search.timed_out() {
+            return false;
+        }
+        if depth == 0 {
+            if twist != 0 || flip != 0 || slice != 0 {
+                return false;
+            }
+            // A phase 1 solution ending with a phase 2 move would have been
+            // found at a lower depth already.
+            if let Some(&face) = search.faces.last() {
+                let m = 3 * face + search.powers.last().unwrap() - 1;
+                if !PHASE2_FORBIDDEN.contains(&m) {
+                    return false;
+                }
+            }
+            return self.start_phase2(search);
+        }
+
+        for face in 0..6 {
+            if is_redundant(&search.faces, face) {
+                continue;
+            }
+            for power in 1..=3 {
+                let m = 3 * face + power - 1;
+                let n_twist = self.coordinate.twist_move(twist, m);
+                let n_flip = self.coordinate.flip_move(flip, m);
+                let n_slice = self.coordinate.fr_to_br_move(slice * 24, m) / 24;
+
+                if self.coordinate.phase1_distance(n_twist, n_flip, n_slice) as usize > depth - 1 {
+                    continue;
+                }
+                search.faces.push(face);
+                search.powers.push(power);
+                if self.phase1(search, n_twist, n_flip, n_slice, depth - 1) {
+                    return true;
+                }
+                search.faces.pop();
+                search.powers.pop();
+            }
         }
+        false
     }
 
-    pub fn solve(&self) {
-        self.cube.print();
-        println!("Is solved? {}", self.cube.is_solved());
+    fn start_phase2(&self, search: &mut Search) -> bool {
+        let phase1_length = search.faces.len();
+        let mut urf_to_dlf = search.urf_to_dlf;
+        let mut fr_to_br = search.fr_to_br;
+        let mut ur_to_ul = search.ur_to_ul;
+        let mut ub_to_df = search.ub_to_df;
+        let mut parity = search.parity;
+
+        for (&face, &power) in search.faces.iter().zip(search.powers.iter()) {
+            let m = 3 * face + power - 1;
+            urf_to_dlf = self.coordinate.urf_to_dlf_move(urf_to_dlf, m);
+            fr_to_br = self.coordinate.fr_to_br_move(fr_to_br, m);
+            ur_to_ul = self.coordinate.ur_to_ul_move(ur_to_ul, m);
+            ub_to_df = self.coordinate.ub_to_df_move(ub_to_df, m);
+            parity = self.coordinate.parity_move(parity, m);
+        }
+        let ur_to_df = self.coordinate.merge_ur_to_ul_and_ub_to_df(ur_to_ul, ub_to_df);
+
+        search.phase1_length = phase1_length;
+        let max_length = self.max_depth as usize - phase1_length;
+        let min_length = self.coordinate.phase2_distance(urf_to_dlf, ur_to_df, fr_to_br, parity) as usize;
+        for depth in min_length..=max_length {
+            if self.phase2(search, urf_to_dlf, ur_to_df, fr_to_br, parity, depth) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn phase2(&self, search: &mut Search, urf_to_dlf: u32, ur_to_df: u32, slice: u32, parity: u32, depth: usize) -> bool {
+        search.nodes += 1;
+        if search.timed_out() {
+            return false;
+        }
+        if depth == 0 {
+            return urf_to_dlf == 0 && ur_to_df == 0 && slice == 0;
+        }
+
+        for face in 0..6 {
+            if is_redundant(&search.faces, face) {
+                continue;
+            }
+            for power in 1..=3 {
+                let m = 3 * face + power - 1;
+                if PHASE2_FORBIDDEN.contains(&m) {
+                    continue;
+                }
+                let n_urf_to_dlf = self.coordinate.urf_to_dlf_move(urf_to_dlf, m);
+                let n_ur_to_df = self.coordinate.ur_to_df_move(ur_to_df, m);
+                let n_slice = self.coordinate.fr_to_br_move(slice, m);
+                let n_parity = self.coordinate.parity_move(parity, m);
+
+                if self.coordinate.phase2_distance(n_urf_to_dlf, n_ur_to_df, n_slice, n_parity) as usize > depth - 1 {
+                    continue;
+                }
+                search.faces.push(face);
+                search.powers.push(power);
+                if self.phase2(search, n_urf_to_dlf, n_ur_to_df, n_slice, n_parity, depth - 1) {
+                    return true;
+                }
+                search.faces.pop();
+                search.powers.pop();
+            }
+        }
+        false
+    }
+}
+
+/// Turning the same face twice in a row, or two opposite faces in both
+/// orders, never leads to a shorter solution.
+fn is_redundant(faces: &[usize], face: usize) -> bool {
+    match faces.last() {
+        Some(&last) => face == last || face + 3 == last,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use coordinate::Coordinate;
+    use cube::Cube;
+    use solver::Solver;
+
+    #[test]
+    fn solve_to_reaches_the_target() {
+        let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+        coordinate.init_pruning();
+        let solver = Solver::new(&coordinate);
+
+        for seed in 0..5 {
+            let (mut start, target) = (Cube::random(2 * seed), Cube::random(2 * seed + 1));
+            let moves = solver.solve_to(&start, &target).unwrap();
+
+            start.multiply_by(&Cube::from_shuffle_sequence(moves));
+            assert!(start == target);
+        }
     }
 }
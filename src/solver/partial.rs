@@ -0,0 +1,359 @@
+use std::collections::VecDeque;
+
+use cube::Cube;
+use cube::corners::Corner;
+use cube::edges::Edge;
+use move_::Move;
+use move_::Move_;
+use move_::UserMove;
+use solver::is_redundant;
+
+/// Maximum number of pieces tracked by one pattern table.
+/// Keeping it at four gives the cross edges, the middle layer edges and the
+/// D corners a table each, which are quick to build.
+const GROUP_SIZE: usize = 4;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum PieceGoal {
+    /// The piece can end up anywhere.
+    Ignored,
+    /// The piece can end up anywhere, as long as it is oriented.
+    Oriented,
+    /// The piece must be back in its slot and oriented.
+    Solved,
+}
+
+/// A partial state to reach, given for each corner and edge.
+#[derive(Clone)]
+pub struct Goal {
+    corners: [PieceGoal; 8],
+    edges: [PieceGoal; 12],
+}
+
+impl Goal {
+    /// Every piece is ignored.
+    pub fn new() -> Self {
+        Self {
+            corners: [PieceGoal::Ignored; 8],
+            edges: [PieceGoal::Ignored; 12],
+        }
+    }
+
+    pub fn solved() -> Self {
+        Self {
+            corners: [PieceGoal::Solved; 8],
+            edges: [PieceGoal::Solved; 12],
+        }
+    }
+
+    /// The four D edges.
+    pub fn cross() -> Self {
+        use cube::edges::Edge::*;
+        Self::new()
+            .edge(DR, PieceGoal::Solved)
+            .edge(DF, PieceGoal::Solved)
+            .edge(DL, PieceGoal::Solved)
+            .edge(DB, PieceGoal::Solved)
+    }
+
+    /// The cross plus the pair made of a D corner and its middle layer edge.
+    pub fn f2l_pair(corner: Corner, edge: Edge) -> Self {
+        Self::cross()
+            .corner(corner, PieceGoal::Solved)
+            .edge(edge, PieceGoal::Solved)
+    }
+
+    /// The first two layers.
+    pub fn f2l() -> Self {
+        use cube::corners::Corner::*;
+        use cube::edges::Edge::*;
+        Self::f2l_pair(DFR, FR)
+            .add(&Self::f2l_pair(DLF, FL))
+            .add(&Self::f2l_pair(DBL, BL))
+            .add(&Self::f2l_pair(DRB, BR))
+    }
+
+    /// The first two layers with every U piece oriented.
+    pub fn oll() -> Self {
+        use cube::corners::Corner::*;
+        use cube::edges::Edge::*;
+        let mut goal = Self::f2l();
+        for &c in &[URF, UFL, ULB, UBR] {
+            goal = goal.corner(c, PieceGoal::Oriented);
+        }
+        for &e in &[UR, UF, UL, UB] {
+            goal = goal.edge(e, PieceGoal::Oriented);
+        }
+        goal
+    }
+
+    pub fn corner(mut self, corner: Corner, goal: PieceGoal) -> Self {
+        self.corners[usize::from(corner)] = goal;
+        self
+    }
+
+    pub fn edge(mut self, edge: Edge, goal: PieceGoal) -> Self {
+        self.edges[usize::from(edge)] = goal;
+        self
+    }
+
+    /// Merge two goals, keeping the strongest requirement for every piece.
+    pub fn add(mut self, other: &Goal) -> Self {
+        for x in 0..8 {
+            self.corners[x] = Self::strongest(self.corners[x], other.corners[x]);
+        }
+        for x in 0..12 {
+            self.edges[x] = Self::strongest(self.edges[x], other.edges[x]);
+        }
+        self
+    }
+
+    fn strongest(a: PieceGoal, b: PieceGoal) -> PieceGoal {
+        match (a, b) {
+            (PieceGoal::Solved, _) | (_, PieceGoal::Solved) => PieceGoal::Solved,
+            (PieceGoal::Oriented, _) | (_, PieceGoal::Oriented) => PieceGoal::Oriented,
+            _ => PieceGoal::Ignored,
+        }
+    }
+
+    pub fn is_reached(&self, cube: &Cube) -> bool {
+        let corners = cube.corners();
+        let edges = cube.edges();
+
+        (0..8).all(|slot| Self::piece_ok(self.corners[usize::from(corners.permutations[slot])],
+                                          usize::from(corners.permutations[slot]) == slot,
+                                          corners.orientations[slot]))
+        && (0..12).all(|slot| Self::piece_ok(self.edges[usize::from(edges.permutations[slot])],
+                                              usize::from(edges.permutations[slot]) == slot,
+                                              edges.orientations[slot]))
+    }
+
+    fn piece_ok(goal: PieceGoal, home: bool, orientation: u8) -> bool {
+        match goal {
+            PieceGoal::Ignored => true,
+            PieceGoal::Oriented => orientation == 0,
+            PieceGoal::Solved => home && orientation == 0,
+        }
+    }
+}
+
+/// Pieces of the same kind sharing a pattern table.
+///
+/// The state of one piece is `slot * orientations + orientation`.
+struct Group {
+    pieces: Vec<usize>,
+    goals: Vec<PieceGoal>,
+    slots: usize,
+    orientations: usize,
+    distance: Vec<u8>,
+}
+
+impl Group {
+    fn index(&self, states: &[u8]) -> usize {
+        let base = self.slots * self.orientations;
+        states.iter().rev().fold(0, |index, &state| index * base + state as usize)
+    }
+
+    fn is_goal(&self, states: &[u8]) -> bool {
+        states.iter().zip(self.goals.iter()).enumerate().all(|(i, (&state, &goal))| {
+            let slot = state as usize / self.orientations;
+            let orientation = state as usize % self.orientations;
+            let distinct = states[..i].iter().all(|&other| other as usize / self.orientations != slot);
+
+            distinct && match goal {
+                PieceGoal::Ignored => true,
+                PieceGoal::Oriented => orientation == 0,
+                PieceGoal::Solved => slot == self.pieces[i] && orientation == 0,
+            }
+        })
+    }
+
+    /// Breadth first search from every goal state, filling `distance`.
+    fn init_distance(&mut self, transitions: &[Vec<u8>]) {
+        let base = self.slots * self.orientations;
+        let size = base.pow(self.pieces.len() as u32);
+        let mut queue = VecDeque::new();
+        let mut states = vec![0; self.pieces.len()];
+        let mut next = vec![0; self.pieces.len()];
+
+        self.distance = vec![0xff; size];
+        for index in 0..size {
+            let mut rest = index;
+            for state in states.iter_mut() {
+                *state = (rest % base) as u8;
+                rest /= base;
+            }
+            if self.is_goal(&states) {
+                self.distance[index] = 0;
+                queue.push_back(index);
+            }
+        }
+
+        while let Some(index) = queue.pop_front() {
+            let mut rest = index;
+            for state in states.iter_mut() {
+                *state = (rest % base) as u8;
+                rest /= base;
+            }
+            for transition in transitions {
+                for (n, &state) in next.iter_mut().zip(states.iter()) {
+                    *n = transition[state as usize];
+                }
+                let next_index = self.index(&next);
+                if self.distance[next_index] == 0xff {
+                    self.distance[next_index] = self.distance[index] + 1;
+                    queue.push_back(next_index);
+                }
+            }
+        }
+    }
+}
+
+/// Optimal solver for a partial `Goal`.
+///
+/// The pattern tables are built once in `new`, so the same solver can be
+/// reused for any number of scrambles.
+pub struct PartialSolver {
+    max_depth: u8,
+    goal: Goal,
+    corner_transitions: Vec<Vec<u8>>,
+    edge_transitions: Vec<Vec<u8>>,
+    corner_groups: Vec<Group>,
+    edge_groups: Vec<Group>,
+}
+
+impl PartialSolver {
+    pub fn new(goal: Goal) -> Self {
+        let mut corner_transitions = Vec::new();
+        let mut edge_transitions = Vec::new();
+
+        for face in 0..6 {
+            let m = Move_::move_definition(Move::from_u(face));
+            let mut corners: Vec<u8> = (0..8 * 3).collect();
+            let mut edges: Vec<u8> = (0..12 * 2).collect();
+
+            for _ in 0..3 {
+                corners = corners.iter().map(|&state| {
+                    let (slot, orientation) = (state as usize / 3, state as usize % 3);
+                    let new_slot = m.corners_permutation.iter().position(|&c| usize::from(c) == slot).unwrap();
+                    (new_slot * 3 + (orientation + m.corners_orientation[new_slot] as usize) % 3) as u8
+                }).collect();
+                edges = edges.iter().map(|&state| {
+                    let (slot, orientation) = (state as usize / 2, state as usize % 2);
+                    let new_slot = m.edges_permutation.iter().position(|&e| usize::from(e) == slot).unwrap();
+                    (new_slot * 2 + (orientation + m.edges_orientation[new_slot] as usize) % 2) as u8
+                }).collect();
+                corner_transitions.push(corners.clone());
+                edge_transitions.push(edges.clone());
+            }
+        }
+
+        let mut corner_groups = Self::groups(&goal.corners, 8, 3);
+        let mut edge_groups = Self::groups(&goal.edges, 12, 2);
+        for group in corner_groups.iter_mut() {
+            group.init_distance(&corner_transitions);
+        }
+        for group in edge_groups.iter_mut() {
+            group.init_distance(&edge_transitions);
+        }
+
+        Self {
+            max_depth: 20,
+            goal: goal,
+            corner_transitions: corner_transitions,
+            edge_transitions: edge_transitions,
+            corner_groups: corner_groups,
+            edge_groups: edge_groups,
+        }
+    }
+
+    fn groups(goals: &[PieceGoal], slots: usize, orientations: usize) -> Vec<Group> {
+        let tracked: Vec<usize> = (0..goals.len()).filter(|&x| goals[x] != PieceGoal::Ignored).collect();
+
+        tracked.chunks(GROUP_SIZE).map(|pieces| Group {
+            pieces: pieces.to_vec(),
+            goals: pieces.iter().map(|&x| goals[x]).collect(),
+            slots: slots,
+            orientations: orientations,
+            distance: Vec::new(),
+        }).collect()
+    }
+
+    pub fn goal(&self) -> &Goal {
+        &self.goal
+    }
+
+    /// Find a shortest sequence reaching the goal from `cube`.
+    pub fn solve(&self, cube: &Cube) -> Option<Vec<(UserMove, usize)>> {
+        let corners = cube.corners();
+        let edges = cube.edges();
+        let corner_states: Vec<Vec<u8>> = self.corner_groups.iter().map(|group| {
+            group.pieces.iter().map(|&piece| {
+                let slot = corners.permutations.iter().position(|&c| usize::from(c) == piece).unwrap();
+                (slot * 3 + corners.orientations[slot] as usize) as u8
+            }).collect()
+        }).collect();
+        let edge_states: Vec<Vec<u8>> = self.edge_groups.iter().map(|group| {
+            group.pieces.iter().map(|&piece| {
+                let slot = edges.permutations.iter().position(|&e| usize::from(e) == piece).unwrap();
+                (slot * 2 + edges.orientations[slot] as usize) as u8
+            }).collect()
+        }).collect();
+
+        let mut faces = Vec::new();
+        let mut powers = Vec::new();
+        let start = self.heuristic(&corner_states, &edge_states);
+        for depth in start..=self.max_depth as usize {
+            if self.search(&corner_states, &edge_states, &mut faces, &mut powers, depth) {
+                return Some(faces.iter().zip(powers.iter())
+                    .map(|(&face, &power)| (UserMove::from_move(Move::from_u(face), power), power))
+                    .collect());
+            }
+        }
+        None
+    }
+
+    fn heuristic(&self, corner_states: &[Vec<u8>], edge_states: &[Vec<u8>]) -> usize {
+        let corners = self.corner_groups.iter().zip(corner_states.iter())
+            .map(|(group, states)| group.distance[group.index(states)]);
+        let edges = self.edge_groups.iter().zip(edge_states.iter())
+            .map(|(group, states)| group.distance[group.index(states)]);
+
+        corners.chain(edges).max().unwrap_or(0) as usize
+    }
+
+    fn search(&self, corner_states: &[Vec<u8>], edge_states: &[Vec<u8>],
+              faces: &mut Vec<usize>, powers: &mut Vec<usize>, depth: usize) -> bool {
+        let distance = self.heuristic(corner_states, edge_states);
+        if distance == 0 {
+            return true;
+        }
+        if distance > depth {
+            return false;
+        }
+
+        for face in 0..6 {
+            if is_redundant(faces, face) {
+                continue;
+            }
+            for power in 1..=3 {
+                let m = 3 * face + power - 1;
+                let corners: Vec<Vec<u8>> = corner_states.iter()
+                    .map(|states| states.iter().map(|&s| self.corner_transitions[m][s as usize]).collect())
+                    .collect();
+                let edges: Vec<Vec<u8>> = edge_states.iter()
+                    .map(|states| states.iter().map(|&s| self.edge_transitions[m][s as usize]).collect())
+                    .collect();
+
+                faces.push(face);
+                powers.push(power);
+                if self.search(&corners, &edges, faces, powers, depth - 1) {
+                    return true;
+                }
+                faces.pop();
+                powers.pop();
+            }
+        }
+        false
+    }
+}
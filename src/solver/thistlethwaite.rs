@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+
+use cube::Cube;
+use move_::Move;
+use move_::UserMove;
+
+const NB_MOVES: usize = 18;
+const NB_FLIP: usize = 2048;
+const NB_TWIST: usize = 2187;
+const NB_SLICE: usize = 495;
+const NB_CORNER_PERMUTATION: usize = 40320;
+const NB_M_SLICE: usize = 495;
+const NB_SLICE_PERMUTATIONS: usize = 13824;
+
+/// Moves of <U, D, L, R, F, B>.
+const G0_MOVES: [usize; 18] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17];
+/// Moves of <U, D, L, R, F2, B2>, which keep edges oriented.
+const G1_MOVES: [usize; 14] = [0, 1, 2, 3, 4, 5, 7, 9, 10, 11, 12, 13, 14, 16];
+/// Moves of <U, D, L2, R2, F2, B2>, which also keep corners oriented and
+/// the E slice edges in the E slice.
+const G2_MOVES: [usize; 10] = [0, 1, 2, 4, 7, 9, 10, 11, 13, 16];
+/// Moves of <U2, D2, L2, R2, F2, B2>.
+const G3_MOVES: [usize; 6] = [1, 4, 7, 10, 13, 16];
+
+/// One reduction step of the algorithm, over a coordinate made of two parts.
+struct Phase {
+    moves: &'static [usize],
+    first_move: Vec<[u32; NB_MOVES]>,
+    second_move: Vec<[u32; NB_MOVES]>,
+    /// Index of the first part among the values reachable in the phase.
+    compact: Vec<u32>,
+    /// Exact number of moves left to reach the next group.
+    distance: Vec<u8>,
+}
+
+impl Phase {
+    fn new(moves: &'static [usize], first_move: Vec<[u32; NB_MOVES]>, second_move: Vec<[u32; NB_MOVES]>,
+           compact: Vec<u32>, goals: &[(u32, u32)]) -> Self {
+        let size = (*compact.iter().filter(|&&x| x != u32::max_value()).max().unwrap() as usize + 1) * second_move.len();
+        let mut phase = Self {
+            moves: moves,
+            first_move: first_move,
+            second_move: second_move,
+            compact: compact,
+            distance: vec![0xff; size],
+        };
+        let mut queue = VecDeque::new();
+
+        for &(first, second) in goals {
+            let index = phase.index(first, second);
+            phase.distance[index] = 0;
+            queue.push_back((first, second));
+        }
+        while let Some((first, second)) = queue.pop_front() {
+            let depth = phase.distance[phase.index(first, second)];
+            for &m in moves {
+                let (n_first, n_second) = (phase.first_move[first as usize][m], phase.second_move[second as usize][m]);
+                let index = phase.index(n_first, n_second);
+                if phase.distance[index] == 0xff {
+                    phase.distance[index] = depth + 1;
+                    queue.push_back((n_first, n_second));
+                }
+            }
+        }
+        phase
+    }
+
+    fn index(&self, first: u32, second: u32) -> usize {
+        self.compact[first as usize] as usize * self.second_move.len() + second as usize
+    }
+
+    /// Follow the distance table down to the next group.
+    fn solve(&self, first: u32, second: u32) -> Option<Vec<usize>> {
+        let (mut first, mut second) = (first, second);
+        let mut moves = Vec::new();
+
+        if self.compact[first as usize] == u32::max_value() || self.distance[self.index(first, second)] == 0xff {
+            return None;
+        }
+        while self.distance[self.index(first, second)] != 0 {
+            let depth = self.distance[self.index(first, second)];
+            for &m in self.moves {
+                let (n_first, n_second) = (self.first_move[first as usize][m], self.second_move[second as usize][m]);
+                if self.distance[self.index(n_first, n_second)] == depth - 1 {
+                    moves.push(m);
+                    first = n_first;
+                    second = n_second;
+                    break;
+                }
+            }
+        }
+        Some(moves)
+    }
+}
+
+/// Thistlethwaite's algorithm, reducing the cube through the groups
+/// G0 = <U, D, L, R, F, B>, G1 = <U, D, L, R, F2, B2>,
+/// G2 = <U, D, L2, R2, F2, B2>, G3 = <U2, D2, L2, R2, F2, B2> and the
+/// solved state. Solutions are longer than the two-phase ones, but every
+/// phase is solved optimally and independently.
+pub struct Thistlethwaite {
+    phases: Vec<Phase>,
+}
+
+impl Thistlethwaite {
+    pub fn new() -> Self {
+        let flip_move = Self::move_table(NB_FLIP, |c, x| c.set_flip(x as i16), |c| c.flip());
+        let twist_move = Self::move_table(NB_TWIST, |c, x| c.set_twist(x as i16), |c| c.twist());
+        let slice_move = Self::move_table(NB_SLICE, |c, x| c.set_fr_to_br(x as i16 * 24), |c| c.fr_to_br() / 24);
+        let corner_move = Self::move_table(NB_CORNER_PERMUTATION, |c, x| c.set_corner_permutation(x), |c| c.corner_permutation());
+        let m_slice_move = Self::move_table(NB_M_SLICE, |c, x| c.set_m_slice(x as i16), |c| c.m_slice());
+        let slice_permutations_move = Self::move_table(NB_SLICE_PERMUTATIONS, |c, x| c.set_slice_permutations(x), |c| c.slice_permutations());
+
+        // Corner permutations of G3 are the ones reachable with half turns.
+        let mut g3_corners = vec![u32::max_value(); NB_CORNER_PERMUTATION];
+        let mut queue = VecDeque::new();
+        let mut count = 0;
+        g3_corners[0] = 0;
+        queue.push_back(0);
+        while let Some(corners) = queue.pop_front() {
+            for &m in G3_MOVES.iter() {
+                let next = corner_move[corners as usize][m];
+                if g3_corners[next as usize] == u32::max_value() {
+                    count += 1;
+                    g3_corners[next as usize] = count;
+                    queue.push_back(next);
+                }
+            }
+        }
+        let solved_m_slice = Cube::new_default().m_slice();
+        let g3_goals: Vec<(u32, u32)> = (0..NB_CORNER_PERMUTATION as u32)
+            .filter(|&x| g3_corners[x as usize] != u32::max_value())
+            .map(|x| (x, solved_m_slice))
+            .collect();
+
+        Self {
+            phases: vec![
+                Phase::new(&G0_MOVES, flip_move, vec![[0; NB_MOVES]], (0..NB_FLIP as u32).collect(), &[(0, 0)]),
+                Phase::new(&G1_MOVES, twist_move, slice_move, (0..NB_TWIST as u32).collect(), &[(0, 0)]),
+                Phase::new(&G2_MOVES, corner_move.clone(), m_slice_move,
+                           (0..NB_CORNER_PERMUTATION as u32).collect(), &g3_goals),
+                Phase::new(&G3_MOVES, corner_move, slice_permutations_move, g3_corners, &[(0, 0)]),
+            ],
+        }
+    }
+
+    fn move_table<S, G>(size: usize, set: S, get: G) -> Vec<[u32; NB_MOVES]>
+        where S: Fn(&mut Cube, u32), G: Fn(&Cube) -> u32
+    {
+        let mut table = vec![[0; NB_MOVES]; size];
+        let mut cube = Cube::new_default();
+
+        for x in 0..size {
+            set(&mut cube, x as u32);
+            for y in 0..6 {
+                for z in 0..3 {
+                    cube.multiply(Move::from_u(y));
+                    table[x][3 * y + z] = get(&cube);
+                }
+                cube.multiply(Move::from_u(y));
+            }
+        }
+        table
+    }
+
+    /// The moves of each of the four phases.
+    pub fn solve_phases(&self, cube: &Cube) -> Option<Vec<Vec<(UserMove, usize)>>> {
+        let mut cube = cube.clone();
+        let mut phases = Vec::new();
+
+        for (n, phase) in self.phases.iter().enumerate() {
+            let (first, second) = match n {
+                0 => (cube.flip(), 0),
+                1 => (cube.twist(), cube.fr_to_br() / 24),
+                2 => (cube.corner_permutation(), cube.m_slice()),
+                _ => (cube.corner_permutation(), cube.slice_permutations()),
+            };
+            let moves: Vec<(UserMove, usize)> = phase.solve(first, second)?.iter()
+                .map(|&m| (UserMove::from_move(Move::from_u(m / 3), m % 3 + 1), m % 3 + 1))
+                .collect();
+
+            cube.apply_sequence(moves.iter().cloned());
+            phases.push(moves);
+        }
+        Some(phases)
+    }
+
+    /// Find a sequence bringing `cube` back to the solved state, merging the
+    /// turns of the same face met where two phases join.
+    pub fn solve(&self, cube: &Cube) -> Option<Vec<(UserMove, usize)>> {
+        let moves: Vec<(UserMove, usize)> = self.solve_phases(cube)?.into_iter()
+            .flat_map(|moves| moves.into_iter())
+            .collect();
+
+        Some(UserMove::simplify_sequence(&moves))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cube::Cube;
+    use solver::thistlethwaite::Thistlethwaite;
+
+    #[test]
+    fn solve_random_states() {
+        let solver = Thistlethwaite::new();
+
+        for seed in 0..5 {
+            let mut cube = Cube::random(seed);
+            let solution = solver.solve(&cube).unwrap();
+
+            cube.multiply_by(&Cube::from_shuffle_sequence(solution));
+            assert!(cube.is_solved());
+        }
+    }
+}
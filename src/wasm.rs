@@ -0,0 +1,108 @@
+//! Functions exported by the `wasm32-unknown-unknown` build, wrapped for
+//! browsers by `www/rubik.js`.
+//!
+//! Strings go through the module memory: the caller copies its input into a
+//! buffer from `rubik_alloc`, and reads the output of the last call at
+//! `rubik_output_ptr` for `rubik_output_len` bytes.
+
+use std::cell::RefCell;
+use std::mem;
+use std::slice;
+use std::str;
+
+use coordinate::Coordinate;
+use coordinate::store::Blob;
+use cube::Cube;
+use cube::color::ColorScheme;
+use move_::UserMove;
+use solver::Solver;
+
+thread_local! {
+    static COORDINATE: RefCell<Option<Coordinate>> = RefCell::new(None);
+    static OUTPUT: RefCell<String> = RefCell::new(String::new());
+}
+
+fn set_output(output: String) {
+    OUTPUT.with(|x| *x.borrow_mut() = output);
+}
+
+/// A buffer of `len` bytes, for the caller to fill in.
+#[no_mangle]
+pub extern "C" fn rubik_alloc(len: usize) -> *mut u8 {
+    let mut buffer = Vec::with_capacity(len);
+    let ptr = buffer.as_mut_ptr();
+
+    mem::forget(buffer);
+    ptr
+}
+
+/// Free a buffer from `rubik_alloc`.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_dealloc(ptr: *mut u8, len: usize) {
+    drop(Vec::from_raw_parts(ptr, 0, len));
+}
+
+#[no_mangle]
+pub extern "C" fn rubik_output_ptr() -> *const u8 {
+    OUTPUT.with(|x| x.borrow().as_ptr())
+}
+
+#[no_mangle]
+pub extern "C" fn rubik_output_len() -> usize {
+    OUTPUT.with(|x| x.borrow().len())
+}
+
+/// Load the tables from a blob written by `rubik tables`, or generate them
+/// if `len` is 0. Must be called before `rubik_solve`.
+///
+/// Returns 1 if the blob is invalid.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_load_tables(ptr: *const u8, len: usize) -> u32 {
+    let mut blob = if len == 0 {
+        Blob::new()
+    } else {
+        match Blob::from_bytes(slice::from_raw_parts(ptr, len)) {
+            Some(blob) => blob,
+            None => return 1,
+        }
+    };
+    let mut coordinate = Coordinate::from_cube(&Cube::new_default());
+
+    coordinate.init_pruning_from(&mut blob);
+    COORDINATE.with(|x| *x.borrow_mut() = Some(coordinate));
+    0
+}
+
+/// Solve the cube with the facelets at `ptr`, in the western color scheme.
+///
+/// Returns 0 with the solution as output, or 1 with the error.
+#[no_mangle]
+pub unsafe extern "C" fn rubik_solve(ptr: *const u8, len: usize) -> u32 {
+    let facelets = str::from_utf8(slice::from_raw_parts(ptr, len)).unwrap_or("");
+    let result = Cube::from_facelets(facelets, &ColorScheme::western())
+        .map_err(|e| e.to_string())
+        .and_then(|cube| COORDINATE.with(|x| match *x.borrow() {
+            Some(ref coordinate) => Solver::new(coordinate).solve(&cube).ok_or_else(|| "no solution found".to_string()),
+            None => Err("tables not loaded".to_string()),
+        }));
+
+    match result {
+        Ok(solution) => {
+            set_output(UserMove::sequence_to_string(&solution));
+            0
+        },
+        Err(error) => {
+            set_output(error);
+            1
+        },
+    }
+}
+
+/// A random scramble of `length` moves as output. The seed is split in two
+/// as JavaScript numbers cannot hold 64 bits integers.
+#[no_mangle]
+pub extern "C" fn rubik_random_sequence(length: u32, seed_high: u32, seed_low: u32) {
+    let seed = u64::from(seed_high) << 32 | u64::from(seed_low);
+
+    set_output(UserMove::sequence_to_string(&UserMove::random_sequence(length as usize, seed)));
+}
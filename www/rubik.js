@@ -0,0 +1,61 @@
+// Browser bindings to the WebAssembly build of rubik:
+//
+//     const rubik = await Rubik.load('rubik.wasm', 'tables.bin');
+//     const scramble = rubik.scramble();
+//     const solution = rubik.solve(facelets);
+//
+// Without a tables URL, the pruning tables are generated in the page, which
+// takes a few seconds.
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+export class Rubik {
+  constructor(instance) {
+    this.exports = instance.exports;
+  }
+
+  static async load(wasmUrl, tablesUrl) {
+    const { instance } = await WebAssembly.instantiateStreaming(fetch(wasmUrl));
+    const rubik = new Rubik(instance);
+    const tables = tablesUrl ? new Uint8Array(await (await fetch(tablesUrl)).arrayBuffer()) : new Uint8Array(0);
+
+    if (rubik.withInput(tables, (ptr, len) => rubik.exports.rubik_load_tables(ptr, len)) !== 0) {
+      throw new Error('invalid pruning tables');
+    }
+    return rubik;
+  }
+
+  // Copy `bytes` into the module memory for the duration of `f`.
+  withInput(bytes, f) {
+    const ptr = this.exports.rubik_alloc(bytes.length);
+    new Uint8Array(this.exports.memory.buffer, ptr, bytes.length).set(bytes);
+    try {
+      return f(ptr, bytes.length);
+    } finally {
+      this.exports.rubik_dealloc(ptr, bytes.length);
+    }
+  }
+
+  output() {
+    const ptr = this.exports.rubik_output_ptr();
+    const len = this.exports.rubik_output_len();
+    return decoder.decode(new Uint8Array(this.exports.memory.buffer, ptr, len));
+  }
+
+  // The solution of the cube with these 54 sticker colors, faces U, R, F, D,
+  // L and B row by row, in the western color scheme.
+  solve(facelets) {
+    const status = this.withInput(encoder.encode(facelets), (ptr, len) => this.exports.rubik_solve(ptr, len));
+    if (status !== 0) {
+      throw new Error(this.output());
+    }
+    return this.output();
+  }
+
+  scramble(length = 25) {
+    const seed = () => Math.floor(Math.random() * 0x100000000);
+    this.exports.rubik_random_sequence(length, seed(), seed());
+    return this.output();
+  }
+}